@@ -143,9 +143,9 @@ pub(crate) fn render<P: AsRef<Path>>(
 }
 
 /// Runs any tests/code examples in the markdown file `input`.
-pub(crate) fn test(options: Options) -> Result<(), String> {
-    let input_str = read_to_string(&options.input)
-        .map_err(|err| format!("{input}: {err}", input = options.input.display()))?;
+pub(crate) fn test(input: &Path, options: Options) -> Result<(), String> {
+    let input_str =
+        read_to_string(input).map_err(|err| format!("{input}: {err}", input = input.display()))?;
     let mut opts = GlobalTestOptions::default();
     opts.no_crate_inject = true;
 
@@ -155,12 +155,12 @@ pub(crate) fn test(options: Options) -> Result<(), String> {
     generate_args_file(&file_path, &options)?;
 
     let mut collector = Collector::new(
-        options.input.display().to_string(),
+        input.display().to_string(),
         options.clone(),
         true,
         opts,
         None,
-        Some(options.input),
+        Some(input.to_owned()),
         options.enable_per_target_ignores,
         file_path,
     );
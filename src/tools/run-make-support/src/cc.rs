@@ -1,6 +1,6 @@
 use std::env;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output};
 
 use crate::{bin_name, cygpath_windows, handle_failed_output, is_msvc, is_windows, tmp_dir, uname};
 
@@ -71,6 +71,10 @@ pub fn out_exe(&mut self, name: &str) -> &mut Self {
 
         self
     }
+
+    fn command_output(&mut self) -> Output {
+        self.cmd.output().expect("failed to get output of finished process")
+    }
 }
 
 /// `EXTRACFLAGS`
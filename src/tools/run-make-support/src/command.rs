@@ -0,0 +1,235 @@
+use std::ffi::OsStr;
+use std::process::{Command, ExitStatus, Output};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::diagnostics::{parse_diagnostics, Diagnostic};
+use crate::rustc;
+
+/// The captured output of a finished process, with assertions which can be chained:
+///
+/// ```ignore (illustrative)
+/// rustdoc()
+///     .input("foo.rs")
+///     .run_and_capture()
+///     .assert_exit_code(0)
+///     .assert_stderr_contains("warning");
+/// ```
+#[derive(Debug)]
+pub struct CompletedProcess {
+    output: Output,
+    timed_out: bool,
+}
+
+impl CompletedProcess {
+    pub(crate) fn new(output: Output, timed_out: bool) -> Self {
+        CompletedProcess { output, timed_out }
+    }
+
+    /// The standard output, which must be valid UTF-8.
+    #[track_caller]
+    pub fn stdout_utf8(&self) -> String {
+        String::from_utf8(self.output.stdout.clone()).expect("stdout is not valid UTF-8")
+    }
+
+    /// The standard error, which must be valid UTF-8.
+    #[track_caller]
+    pub fn stderr_utf8(&self) -> String {
+        String::from_utf8(self.output.stderr.clone()).expect("stderr is not valid UTF-8")
+    }
+
+    /// The standard output followed by the standard error, which must be valid UTF-8. They are
+    /// only in the order they were written when captured with
+    /// [`Rustdoc::capture_combined`](crate::Rustdoc::capture_combined), which puts everything in
+    /// the standard output.
+    #[track_caller]
+    pub fn combined_utf8(&self) -> String {
+        self.stdout_utf8() + &self.stderr_utf8()
+    }
+
+    pub fn status(&self) -> ExitStatus {
+        self.output.status
+    }
+
+    /// The standard error with the sysroot replaced by `$SYSROOT`, then each `from` of
+    /// `replacements` by its `to`, in order, to compare it with an expected output, e.g.
+    /// `&[(tmp_dir().to_str().unwrap(), "$TMPDIR")]`.
+    #[track_caller]
+    pub fn normalized_stderr(&self, replacements: &[(&str, &str)]) -> String {
+        static SYSROOT: OnceLock<String> = OnceLock::new();
+        let sysroot = SYSROOT.get_or_init(|| {
+            let output = rustc().arg("--print").arg("sysroot").run();
+            String::from_utf8(output.stdout).unwrap().trim_end().to_owned()
+        });
+        let mut stderr = self.stderr_utf8().replace(sysroot.as_str(), "$SYSROOT");
+        for (from, to) in replacements {
+            stderr = stderr.replace(from, to);
+        }
+        stderr
+    }
+
+    /// Whether the process was killed for running longer than its timeout.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// The raw [`Output`] of the process.
+    pub fn output(&self) -> &Output {
+        &self.output
+    }
+
+    #[track_caller]
+    pub fn assert_stdout_contains(&self, needle: &str) -> &Self {
+        let stdout = self.stdout_utf8();
+        assert!(stdout.contains(needle), "stdout does not contain `{needle}`:\n{stdout}");
+        self
+    }
+
+    #[track_caller]
+    pub fn assert_stderr_contains(&self, needle: &str) -> &Self {
+        let stderr = self.stderr_utf8();
+        assert!(stderr.contains(needle), "stderr does not contain `{needle}`:\n{stderr}");
+        self
+    }
+
+    /// Check that the standard output matches the regular expression `pattern` somewhere.
+    #[track_caller]
+    pub fn assert_stdout_matches_regex(&self, pattern: &str) -> &Self {
+        let stdout = self.stdout_utf8();
+        assert!(compile(pattern).is_match(&stdout), "stdout does not match `{pattern}`:\n{stdout}");
+        self
+    }
+
+    /// Check that the standard error matches the regular expression `pattern` somewhere.
+    #[track_caller]
+    pub fn assert_stderr_matches_regex(&self, pattern: &str) -> &Self {
+        let stderr = self.stderr_utf8();
+        assert!(compile(pattern).is_match(&stderr), "stderr does not match `{pattern}`:\n{stderr}");
+        self
+    }
+
+    /// The groups captured by the first match of the regular expression `pattern` in the
+    /// standard output, without the whole match. A group which didn't participate in the match is
+    /// an empty string. `None` if `pattern` doesn't match.
+    #[track_caller]
+    pub fn stdout_captures(&self, pattern: &str) -> Option<Vec<String>> {
+        let stdout = self.stdout_utf8();
+        let captures = compile(pattern).captures(&stdout)?;
+        let groups = captures.iter().skip(1);
+        Some(
+            groups
+                .map(|group| group.map_or_else(String::new, |m| m.as_str().to_string()))
+                .collect(),
+        )
+    }
+
+    /// The number of warnings in the JSON diagnostics of the standard error, not counting the
+    /// `N warnings emitted` summary. The process must have been run with `--error-format=json`.
+    #[track_caller]
+    pub fn warning_count(&self) -> usize {
+        self.warnings().len()
+    }
+
+    /// Check that the JSON diagnostics of the standard error have no warnings, whatever else
+    /// rustdoc printed there. The process must have been run with `--error-format=json`.
+    #[track_caller]
+    pub fn assert_no_warnings(&self) -> &Self {
+        let warnings = self.warnings();
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:#?}");
+        self
+    }
+
+    /// Check that one of the JSON diagnostics of the standard error has the error code or lint
+    /// name `code`, like `E0601` or `rustdoc::broken_intra_doc_links`. The process must have been
+    /// run with `--error-format=json`.
+    #[track_caller]
+    pub fn assert_has_diagnostic_code(&self, code: &str) -> &Self {
+        let diagnostics = self.json_diagnostics();
+        let codes: Vec<_> = diagnostics.iter().filter_map(|diag| diag.code.as_deref()).collect();
+        assert!(codes.contains(&code), "no diagnostic with the code `{code}`, found {codes:?}");
+        self
+    }
+
+    /// The warnings of [`CompletedProcess::json_diagnostics`], without the summary.
+    #[track_caller]
+    fn warnings(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.json_diagnostics();
+        diagnostics.retain(|diagnostic| diagnostic.level == "warning" && !diagnostic.is_summary());
+        diagnostics
+    }
+
+    /// The JSON diagnostics of the standard error. Panics if it holds human-readable ones
+    /// instead, from a process which wasn't run with `--error-format=json`.
+    #[track_caller]
+    fn json_diagnostics(&self) -> Vec<Diagnostic> {
+        let stderr = self.stderr_utf8();
+        assert!(
+            !stderr.lines().any(|line| line.starts_with("warning") || line.starts_with("error")),
+            "stderr has human-readable diagnostics, run with `--error-format=json`:\n{stderr}"
+        );
+        parse_diagnostics(&stderr)
+    }
+
+    /// Check the exit code; a process killed by a signal has none and never matches.
+    #[track_caller]
+    pub fn assert_exit_code(&self, code: i32) -> &Self {
+        assert_eq!(
+            self.output.status.code(),
+            Some(code),
+            "unexpected exit status `{}`, stderr:\n{}",
+            self.output.status,
+            String::from_utf8_lossy(&self.output.stderr)
+        );
+        self
+    }
+
+    /// The number of the signal which killed the process, if it didn't exit by itself.
+    #[cfg(unix)]
+    pub fn exit_signal(&self) -> Option<i32> {
+        std::os::unix::process::ExitStatusExt::signal(&self.output.status)
+    }
+
+    /// Check that the process exited by itself instead of being killed by a signal, which is how
+    /// some crashes like aborts and segfaults end.
+    #[cfg(unix)]
+    #[track_caller]
+    pub fn assert_no_signal(&self) -> &Self {
+        if let Some(signal) = self.exit_signal() {
+            panic!(
+                "the process was killed by signal {signal}, stderr:\n{}",
+                String::from_utf8_lossy(&self.output.stderr)
+            );
+        }
+        self
+    }
+}
+
+impl From<Output> for CompletedProcess {
+    fn from(output: Output) -> Self {
+        CompletedProcess::new(output, false)
+    }
+}
+
+#[track_caller]
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|err| panic!("invalid regex `{pattern}`: {err}"))
+}
+
+/// Render the program and arguments of `cmd` as a line which can be pasted in a POSIX shell.
+pub(crate) fn shell_line(cmd: &Command) -> String {
+    let words: Vec<_> =
+        std::iter::once(cmd.get_program()).chain(cmd.get_args()).map(shell_quote).collect();
+    words.join(" ")
+}
+
+/// Single-quote `word` unless it is made only of characters the shell doesn't interpret.
+fn shell_quote(word: &OsStr) -> String {
+    let word = word.to_string_lossy();
+    let is_plain = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !word.is_empty() && word.chars().all(is_plain) {
+        word.into_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
@@ -0,0 +1,78 @@
+//! Parsing of the diagnostics emitted by `rustc` and `rustdoc` with `--error-format=json`.
+
+use serde_json::Value;
+
+/// A diagnostic emitted with `--error-format=json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The level, like `error` or `warning`.
+    pub level: String,
+    pub message: String,
+    /// The error code or lint name, if the diagnostic has one.
+    pub code: Option<String>,
+    pub spans: Vec<DiagnosticSpan>,
+    /// The diagnostic rendered for humans, in the short format or with colors when requested with
+    /// [`Rustdoc::json_config`](crate::Rustdoc::json_config).
+    pub rendered: Option<String>,
+}
+
+/// A span of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticSpan {
+    pub file_name: String,
+    pub line_start: u64,
+    pub line_end: u64,
+    pub column_start: u64,
+    pub column_end: u64,
+    pub is_primary: bool,
+    pub label: Option<String>,
+}
+
+/// Parse each line of `stderr` holding a JSON diagnostic. Lines which are not JSON, or which are
+/// JSON messages of another kind (like artifact notifications), are skipped.
+pub fn parse_diagnostics(stderr: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|json| Diagnostic::from_json(&json))
+        .collect()
+}
+
+impl Diagnostic {
+    /// Whether this is the `N warnings emitted` summary rather than an actual diagnostic.
+    pub(crate) fn is_summary(&self) -> bool {
+        self.code.is_none()
+            && self.spans.is_empty()
+            && (self.message.ends_with(" warning emitted")
+                || self.message.ends_with(" warnings emitted"))
+    }
+
+    fn from_json(json: &Value) -> Option<Self> {
+        if json.get("$message_type").is_some_and(|ty| ty != "diagnostic") {
+            return None;
+        }
+        let spans = json.get("spans")?.as_array()?;
+        Some(Diagnostic {
+            level: json.get("level")?.as_str()?.to_string(),
+            message: json.get("message")?.as_str()?.to_string(),
+            code: json.get("code").and_then(|code| code.get("code")?.as_str()).map(str::to_string),
+            spans: spans.iter().filter_map(DiagnosticSpan::from_json).collect(),
+            rendered: json.get("rendered").and_then(Value::as_str).map(str::to_string),
+        })
+    }
+}
+
+impl DiagnosticSpan {
+    fn from_json(json: &Value) -> Option<Self> {
+        let number = |key: &str| json.get(key)?.as_u64();
+        Some(DiagnosticSpan {
+            file_name: json.get("file_name")?.as_str()?.to_string(),
+            line_start: number("line_start")?,
+            line_end: number("line_end")?,
+            column_start: number("column_start")?,
+            column_end: number("column_end")?,
+            is_primary: json.get("is_primary")?.as_bool()?,
+            label: json.get("label").and_then(Value::as_str).map(str::to_string),
+        })
+    }
+}
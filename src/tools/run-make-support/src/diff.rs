@@ -0,0 +1,62 @@
+//! Line diffs, to show how an output differs from what was expected.
+
+use std::fmt::Write;
+
+/// Number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+/// Render the changes from `expected` to `actual` as a unified diff.
+pub fn unified_diff(expected: &str, actual: &str) -> String {
+    let lines = diff::lines(expected, actual);
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, diff::Result::Both(..)))
+        .map(|(i, _)| i)
+        .collect();
+
+    // Changes closer than twice the context end up in the same hunk.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    // The line numbers in `expected` and `actual` of each diff line.
+    let mut positions = Vec::with_capacity(lines.len());
+    let (mut expected_line, mut actual_line) = (1, 1);
+    for line in &lines {
+        positions.push((expected_line, actual_line));
+        match line {
+            diff::Result::Left(_) => expected_line += 1,
+            diff::Result::Right(_) => actual_line += 1,
+            diff::Result::Both(..) => {
+                expected_line += 1;
+                actual_line += 1;
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        let expected_len =
+            hunk.iter().filter(|line| !matches!(line, diff::Result::Right(_))).count();
+        let actual_len = hunk.iter().filter(|line| !matches!(line, diff::Result::Left(_))).count();
+        let (expected_start, actual_start) = positions[start];
+        writeln!(output, "@@ -{expected_start},{expected_len} +{actual_start},{actual_len} @@")
+            .unwrap();
+        for line in hunk {
+            match line {
+                diff::Result::Left(line) => writeln!(output, "-{line}").unwrap(),
+                diff::Result::Right(line) => writeln!(output, "+{line}").unwrap(),
+                diff::Result::Both(line, _) => writeln!(output, " {line}").unwrap(),
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,1013 @@
+//! XPath assertions on generated HTML, in the spirit of `src/etc/htmldocck.py` but without
+//! having to shell out to Python.
+//!
+//! Only the subset of XPath used by rustdoc tests is supported: child (`/`) and descendant (`//`)
+//! steps, `*`, `.`, `..`, `text()`, `@attr`, and predicates made of positions, attribute or text
+//! comparisons, nested paths, `and`/`or`, `not(..)`, `contains(..)`, `starts-with(..)` and
+//! `normalize-space(..)`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::diff::unified_diff;
+
+/// Assert that rustdoc generated the page `rel` of the crate `crate_name` in `out_dir`, like
+/// htmldocck's `@has {crate_name}/{rel}` without an XPath.
+#[track_caller]
+pub fn assert_doc_file_exists<P: AsRef<Path>>(out_dir: P, crate_name: &str, rel: &str) {
+    let file = out_dir.as_ref().join(crate_name).join(rel);
+    if !file.exists() {
+        panic!("`{}` was not generated, {}", file.display(), list_parent(&file));
+    }
+}
+
+/// Assert that rustdoc did not generate the page `rel` of the crate `crate_name` in `out_dir`,
+/// like htmldocck's `@!has {crate_name}/{rel}`.
+#[track_caller]
+pub fn assert_doc_file_missing<P: AsRef<Path>>(out_dir: P, crate_name: &str, rel: &str) {
+    let file = out_dir.as_ref().join(crate_name).join(rel);
+    if file.exists() {
+        panic!("`{}` was unexpectedly generated, {}", file.display(), list_parent(&file));
+    }
+}
+
+/// Assert that the documentation at the top of the page `item_rel` of the crate `crate_name` in
+/// `out_dir`, like `struct.Foo.html`, contains `needle` once runs of whitespace are collapsed in
+/// both. Only the item's own doc comment is looked at, not those of its fields or methods.
+#[track_caller]
+pub fn assert_item_doc_contains<P: AsRef<Path>>(
+    out_dir: P,
+    crate_name: &str,
+    item_rel: &str,
+    needle: &str,
+) {
+    let file = out_dir.as_ref().join(crate_name).join(item_rel);
+    if !file.exists() {
+        panic!("`{}` was not generated, {}", file.display(), list_parent(&file));
+    }
+    let needle = normalize_whitespace(needle);
+    let docs = select(&file, "//details[contains(@class, 'top-doc')]/div[@class='docblock']");
+    let [doc] = &docs[..] else {
+        panic!("`{}` has no documentation of the item", file.display());
+    };
+    if !doc.contains(&needle) {
+        panic!("the documentation in `{}` does not contain `{needle}`: {doc:?}", file.display());
+    }
+}
+
+/// Assert that the HTML pages rustdoc generated for the crate `crate_name` in `out_dir` are
+/// exactly `expected`, given relative to `{out_dir}/{crate_name}` with `/` separators, like
+/// `["index.html", "inner/struct.Bar.html"]`. The order doesn't matter.
+#[track_caller]
+pub fn assert_generated_files<P: AsRef<Path>>(out_dir: P, crate_name: &str, expected: &[&str]) {
+    let crate_dir = out_dir.as_ref().join(crate_name);
+    let mut generated = Vec::new();
+    collect_html_files(&crate_dir, "", &mut generated);
+    let extra: Vec<_> =
+        generated.iter().filter(|file| !expected.contains(&file.as_str())).collect();
+    let missing: Vec<_> = expected
+        .iter()
+        .filter(|file| !generated.iter().any(|generated| generated == *file))
+        .collect();
+    if !extra.is_empty() || !missing.is_empty() {
+        panic!(
+            "unexpected pages in `{}`\nextra: {extra:#?}\nmissing: {missing:#?}",
+            crate_dir.display()
+        );
+    }
+}
+
+/// Assert that every relative `href` of the HTML pages under `out_dir` points to a file which
+/// exists, ignoring fragments and queries. URLs with a scheme, like `https:` or `mailto:`, and
+/// absolute paths are not checked. All the broken links are listed before panicking. The links
+/// of `help.html` and `settings.html` to `index.html` only resolve with an index page, see
+/// [`Rustdoc::enable_index_page`](crate::Rustdoc::enable_index_page).
+#[track_caller]
+pub fn assert_links_resolve<P: AsRef<Path>>(out_dir: P) {
+    let out_dir = out_dir.as_ref();
+    let mut pages = Vec::new();
+    collect_html_files(out_dir, "", &mut pages);
+    pages.sort();
+    let mut broken = Vec::new();
+    for page in pages {
+        let file = out_dir.join(&page);
+        let html = fs::read_to_string(&file)
+            .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", file.display()));
+        let document = Document::parse(&html);
+        for node in 0..document.nodes.len() {
+            let Some(href) = document.attr(node, "href") else { continue };
+            let target = href.split(['#', '?']).next().unwrap();
+            let has_scheme = target
+                .split_once(':')
+                .is_some_and(|(scheme, _)| !scheme.is_empty() && !scheme.contains('/'));
+            if target.is_empty() || has_scheme || target.starts_with('/') {
+                continue;
+            }
+            if !file.parent().unwrap().join(target).exists() {
+                broken.push(format!("{page}: {href}"));
+            }
+        }
+    }
+    if !broken.is_empty() {
+        panic!("broken links in `{}`: {broken:#?}", out_dir.display());
+    }
+}
+
+/// Push the path of each HTML file under `dir` to `files`, relative to `dir` and behind `prefix`.
+fn collect_html_files(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let entries =
+        fs::read_dir(dir).unwrap_or_else(|err| panic!("failed to read `{}`: {err}", dir.display()));
+    for entry in entries {
+        let entry = entry.unwrap();
+        let name = format!("{prefix}{}", entry.file_name().to_string_lossy());
+        if entry.file_type().unwrap().is_dir() {
+            collect_html_files(&entry.path(), &format!("{name}/"), files);
+        } else if name.ends_with(".html") {
+            files.push(name);
+        }
+    }
+}
+
+/// Describe what the directory of `file` contains, for the messages of failed assertions.
+fn list_parent(file: &Path) -> String {
+    let dir = file.parent().unwrap();
+    match fs::read_dir(dir) {
+        Ok(entries) => {
+            let mut names: Vec<_> = entries
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            format!("`{}` contains {names:#?}", dir.display())
+        }
+        Err(err) => format!("`{}` can't be read: {err}", dir.display()),
+    }
+}
+
+/// Assert that `xpath` matches at least one node of the HTML `file`.
+#[track_caller]
+pub fn assert_has_xpath<P: AsRef<Path>>(file: P, xpath: &str) {
+    let file = file.as_ref();
+    if select(file, xpath).is_empty() {
+        panic!("`{xpath}` did not match anything in `{}`", file.display());
+    }
+}
+
+/// Assert that the text of a node matched by `xpath` in the HTML `file` contains `expected`, once
+/// runs of whitespace are collapsed in both.
+#[track_caller]
+pub fn assert_xpath_text<P: AsRef<Path>>(file: P, xpath: &str, expected: &str) {
+    let file = file.as_ref();
+    let expected = normalize_whitespace(expected);
+    let texts = select(file, xpath);
+    if !texts.iter().any(|text| text.contains(&expected)) {
+        panic!(
+            "no node matched by `{xpath}` in `{}` contains `{expected}`, found {texts:#?}",
+            file.display()
+        );
+    }
+}
+
+/// Assert that `xpath` matches nothing in the HTML `file`, like htmldocck's `@!has`.
+#[track_caller]
+pub fn assert_no_xpath<P: AsRef<Path>>(file: P, xpath: &str) {
+    let file = file.as_ref();
+    let texts = select(file, xpath);
+    if !texts.is_empty() {
+        panic!("`{xpath}` unexpectedly matched in `{}`: {texts:#?}", file.display());
+    }
+}
+
+/// Assert that an element matched by `xpath` in the HTML `file` has the attribute `attr` with
+/// exactly the value `expected`, like `assert_xpath_attr(file, "//a[@class='fn']", "href", ..)`.
+#[track_caller]
+pub fn assert_xpath_attr<P: AsRef<Path>>(file: P, xpath: &str, attr: &str, expected: &str) {
+    let file = file.as_ref();
+    let (document, matches) = select_matches(file, xpath);
+    let elements: Vec<_> = matches
+        .iter()
+        .filter_map(|m| match m {
+            Match::Node(node) => Some(*node),
+            Match::Attr(..) => None,
+        })
+        .collect();
+    if elements.is_empty() {
+        panic!("`{xpath}` did not match any element in `{}`", file.display());
+    }
+    let values: Vec<_> = elements.iter().filter_map(|&node| document.attr(node, attr)).collect();
+    if values.is_empty() {
+        panic!(
+            "none of the {} elements matched by `{xpath}` in `{}` has an `{attr}` attribute",
+            elements.len(),
+            file.display()
+        );
+    }
+    if !values.contains(&expected) {
+        panic!(
+            "the `{attr}` attribute of the elements matched by `{xpath}` in `{}` is not \
+            `{expected}`, found {values:#?}",
+            file.display()
+        );
+    }
+}
+
+/// Assert that no node matched by `xpath` in the HTML `file` has a text containing `forbidden`,
+/// once runs of whitespace are collapsed in both.
+#[track_caller]
+pub fn assert_no_xpath_text<P: AsRef<Path>>(file: P, xpath: &str, forbidden: &str) {
+    let file = file.as_ref();
+    let forbidden = normalize_whitespace(forbidden);
+    if let Some(text) = select(file, xpath).iter().find(|text| text.contains(&forbidden)) {
+        panic!(
+            "a node matched by `{xpath}` in `{}` contains `{forbidden}`: `{text}`",
+            file.display()
+        );
+    }
+}
+
+/// Assert that `xpath` matches exactly `expected` nodes of the HTML `file`, like htmldocck's
+/// `@count`.
+#[track_caller]
+pub fn assert_xpath_count<P: AsRef<Path>>(file: P, xpath: &str, expected: usize) {
+    let file = file.as_ref();
+    let count = select(file, xpath).len();
+    if count != expected {
+        panic!("`{xpath}` matched {count} nodes in `{}`, expected {expected}", file.display());
+    }
+}
+
+/// Assert that the source page `src_file` links at least one identifier to its definition, as
+/// rustdoc does with `--generate-link-to-definition`. Links to the lines of the page itself don't
+/// count.
+#[track_caller]
+pub fn assert_src_links<P: AsRef<Path>>(src_file: P) {
+    let src_file = src_file.as_ref();
+    if select(src_file, "//pre[@class='rust']//a[not(starts-with(@href, '#'))]").is_empty() {
+        panic!("the source page `{}` doesn't link to any definition", src_file.display());
+    }
+}
+
+/// Assert that the HTML page `html` loads its stylesheets, fonts and icons from `root`, as
+/// rustdoc does with `--static-root-path`. The scripts aren't checked, some of them like
+/// `crates.js` are generated for each invocation and stay relative to the page.
+#[track_caller]
+pub fn assert_static_root<P: AsRef<Path>>(html: P, root: &str) {
+    let html = html.as_ref();
+    let hrefs = select(html, "//link/@href");
+    if hrefs.is_empty() {
+        panic!("`{}` doesn't reference any asset", html.display());
+    }
+    let outside: Vec<_> = hrefs.iter().filter(|href| !href.starts_with(root)).collect();
+    if !outside.is_empty() {
+        panic!("`{}` references assets outside of `{root}`: {outside:#?}", html.display());
+    }
+}
+
+/// Assert that the "Layout" section of the type page `file`, generated with
+/// `--show-type-layout`, displays `expected_size`. A bare number is taken as a number of bytes, so
+/// `"0"` matches the `0 bytes` of zero-sized types and `"1"` matches `1 byte`.
+#[track_caller]
+pub fn assert_type_layout<P: AsRef<Path>>(file: P, expected_size: &str) {
+    let file = file.as_ref();
+    let expected = match expected_size.parse::<u64>() {
+        Ok(1) => "1 byte".to_string(),
+        Ok(size) => format!("{size} bytes"),
+        Err(_) => normalize_whitespace(expected_size),
+    };
+    let sizes = select(file, r#"//h2[@id="layout"]/../div[@class="docblock"]/p[strong="Size:"]"#);
+    let Some(size) = sizes.first() else {
+        panic!(
+            "`{}` has no layout section, was it generated with `--show-type-layout`?",
+            file.display()
+        );
+    };
+    let size = size.trim_start_matches("Size:").trim_start();
+    assert_eq!(size, expected, "unexpected size in the layout section of `{}`", file.display());
+}
+
+/// Assert that the HTML `generated` file matches the `golden` one, ignoring whitespace between
+/// tags, and print a diff of the two otherwise. When `RUSTC_BLESS_TEST` is set, `golden` is
+/// overwritten with `generated` instead.
+#[track_caller]
+pub fn assert_html_matches_snapshot<P: AsRef<Path>, Q: AsRef<Path>>(generated: P, golden: Q) {
+    let (generated, golden) = (generated.as_ref(), golden.as_ref());
+    let actual = fs::read_to_string(generated)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", generated.display()));
+    if env::var_os("RUSTC_BLESS_TEST").is_some() {
+        fs::write(golden, &actual)
+            .unwrap_or_else(|err| panic!("failed to bless `{}`: {err}", golden.display()));
+        return;
+    }
+    let expected = fs::read_to_string(golden)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", golden.display()));
+    let (expected, actual) = (normalize_html(&expected), normalize_html(&actual));
+    if expected != actual {
+        panic!(
+            "`{}` does not match the snapshot `{}`, set `RUSTC_BLESS_TEST` to update it:\n{}",
+            generated.display(),
+            golden.display(),
+            unified_diff(&expected, &actual)
+        );
+    }
+}
+
+/// Drop the whitespace-only text between tags, putting each tag on its own line so that diffs
+/// stay readable.
+fn normalize_html(html: &str) -> String {
+    let mut normalized = String::with_capacity(html.len());
+    let mut rest = html.trim();
+    while let Some(end) = rest.find('>') {
+        let (tag, after) = rest.split_at(end + 1);
+        normalized.push_str(tag);
+        let trimmed = after.trim_start();
+        if trimmed.starts_with('<') {
+            normalized.push('\n');
+            rest = trimmed;
+        } else {
+            rest = after;
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// The normalized text of every match of `xpath` in the HTML `file`.
+#[track_caller]
+fn select(file: &Path, xpath: &str) -> Vec<String> {
+    let (document, matches) = select_matches(file, xpath);
+    matches.iter().map(|m| normalize_whitespace(&m.text(&document))).collect()
+}
+
+/// Parse the HTML `file` and find the matches of `xpath` in it.
+#[track_caller]
+fn select_matches(file: &Path, xpath: &str) -> (Document, Vec<Match>) {
+    let html = fs::read_to_string(file)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", file.display()));
+    let path = XPath::parse(xpath).unwrap_or_else(|err| panic!("invalid XPath `{xpath}`: {err}"));
+    let document = Document::parse(&html);
+    let matches = path.select(&document, &[Document::ROOT]);
+    (document, matches)
+}
+
+/// A parsed HTML document. Nodes live in an arena, in document order.
+struct Document {
+    nodes: Vec<Node>,
+}
+
+struct Node {
+    parent: Option<usize>,
+    children: Vec<usize>,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Document,
+    Element { name: String, attrs: Vec<(String, String)> },
+    Text(String),
+}
+
+/// Elements which never have content, and so no end tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is not parsed as HTML.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+impl Document {
+    const ROOT: usize = 0;
+
+    /// Parse HTML leniently: unbalanced end tags are ignored and missing end tags are implied by
+    /// the end tag of an enclosing element.
+    fn parse(html: &str) -> Self {
+        let mut document = Document {
+            nodes: vec![Node { parent: None, children: Vec::new(), kind: NodeKind::Document }],
+        };
+        let mut open = vec![Self::ROOT];
+        let mut rest = html;
+        while !rest.is_empty() {
+            let parent = *open.last().unwrap();
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            } else if let Some(tag) = rest.strip_prefix("</") {
+                let end = tag.find('>').unwrap_or(tag.len());
+                let name = tag[..end].trim().to_ascii_lowercase();
+                if let Some(depth) = open.iter().rposition(|&node| document.is_element(node, &name))
+                {
+                    open.truncate(depth);
+                }
+                rest = tag.get(end + 1..).unwrap_or("");
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                let (name, attrs, self_closing, after) = parse_start_tag(&rest[1..]);
+                rest = after;
+                let node = document.push(parent, NodeKind::Element { name: name.clone(), attrs });
+                if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                    let close = format!("</{name}");
+                    let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+                    if end > 0 {
+                        document.push(node, NodeKind::Text(rest[..end].to_string()));
+                    }
+                    rest = &rest[end..];
+                } else if !self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+                    open.push(node);
+                }
+            } else {
+                // A `<` which doesn't start a tag is part of the text.
+                let skip = usize::from(rest.starts_with('<'));
+                let end = rest[skip..].find('<').map_or(rest.len(), |end| end + skip);
+                document.push(parent, NodeKind::Text(decode_entities(&rest[..end])));
+                rest = &rest[end..];
+            }
+        }
+        document
+    }
+
+    fn push(&mut self, parent: usize, kind: NodeKind) -> usize {
+        let node = self.nodes.len();
+        self.nodes.push(Node { parent: Some(parent), children: Vec::new(), kind });
+        self.nodes[parent].children.push(node);
+        node
+    }
+
+    fn is_element(&self, node: usize, expected: &str) -> bool {
+        matches!(&self.nodes[node].kind, NodeKind::Element { name, .. } if name == expected)
+    }
+
+    fn attr(&self, node: usize, expected: &str) -> Option<&str> {
+        match &self.nodes[node].kind {
+            NodeKind::Element { attrs, .. } => {
+                attrs.iter().find(|(name, _)| name == expected).map(|(_, value)| value.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// The concatenation of all the text under `node`.
+    fn text(&self, node: usize) -> String {
+        let mut text = String::new();
+        self.collect_text(node, &mut text);
+        text
+    }
+
+    fn collect_text(&self, node: usize, text: &mut String) {
+        match &self.nodes[node].kind {
+            NodeKind::Text(content) => text.push_str(content),
+            _ => {
+                for &child in &self.nodes[node].children {
+                    self.collect_text(child, text);
+                }
+            }
+        }
+    }
+
+    /// `node` and all the nodes under it, in document order.
+    fn descendants_or_self(&self, node: usize, nodes: &mut Vec<usize>) {
+        nodes.push(node);
+        for &child in &self.nodes[node].children {
+            self.descendants_or_self(child, nodes);
+        }
+    }
+}
+
+/// Parse the tag name and attributes following a `<`, returning them along with whether the tag
+/// is self-closing and the input after the tag.
+fn parse_start_tag(input: &str) -> (String, Vec<(String, String)>, bool, &str) {
+    let is_name_end = |c: char| c.is_ascii_whitespace() || c == '>' || c == '/';
+    let end = input.find(is_name_end).unwrap_or(input.len());
+    let name = input[..end].to_ascii_lowercase();
+    let mut rest = &input[end..];
+    let mut attrs = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (name, attrs, true, after);
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return (name, attrs, false, after);
+        } else if rest.is_empty() {
+            return (name, attrs, false, rest);
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+        let end = rest.find(|c: char| is_name_end(c) || c == '=').unwrap_or(rest.len());
+        let attr = rest[..end].to_ascii_lowercase();
+        rest = rest[end..].trim_start();
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            rest = after.trim_start();
+            let end = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    rest = &rest[1..];
+                    let end = rest.find(quote).unwrap_or(rest.len());
+                    value = decode_entities(&rest[..end]);
+                    (end + 1).min(rest.len())
+                }
+                _ => {
+                    let end = rest.find(|c: char| c.is_ascii_whitespace() || c == '>');
+                    let end = end.unwrap_or(rest.len());
+                    value = decode_entities(&rest[..end]);
+                    end
+                }
+            };
+            rest = &rest[end..];
+        }
+        attrs.push((attr, value));
+    }
+}
+
+/// Decode the character references rustdoc emits.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = entity.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// A node selected by an XPath: either a document node or the value of an attribute.
+#[derive(Debug, Clone, PartialEq)]
+enum Match {
+    Node(usize),
+    Attr(usize, String),
+}
+
+impl Match {
+    fn text(&self, document: &Document) -> String {
+        match self {
+            Match::Node(node) => document.text(*node),
+            Match::Attr(_, value) => value.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct XPath {
+    absolute: bool,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+struct Step {
+    /// Whether the step is preceded by `//`.
+    descendant: bool,
+    test: NodeTest,
+    predicates: Vec<Expr>,
+}
+
+#[derive(Debug)]
+enum NodeTest {
+    SelfNode,
+    Parent,
+    Element(Option<String>),
+    Text,
+    AnyNode,
+    Attr(String),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Eq(Box<Expr>, Box<Expr>, bool),
+    Not(Box<Expr>),
+    Contains(Box<Expr>, Box<Expr>),
+    StartsWith(Box<Expr>, Box<Expr>),
+    NormalizeSpace(Box<Expr>),
+    Path(XPath),
+    Str(String),
+    Num(usize),
+}
+
+enum Value {
+    Matches(Vec<Match>),
+    Str(String),
+    Bool(bool),
+    Num(usize),
+}
+
+impl Value {
+    fn to_bool(&self) -> bool {
+        match self {
+            Value::Matches(matches) => !matches.is_empty(),
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::Num(n) => *n != 0,
+        }
+    }
+
+    /// The string values of this value; node sets compare equal if any of their nodes does.
+    fn strings(&self, document: &Document) -> Vec<String> {
+        match self {
+            Value::Matches(matches) => matches.iter().map(|m| m.text(document)).collect(),
+            Value::Str(s) => vec![s.clone()],
+            Value::Bool(b) => vec![b.to_string()],
+            Value::Num(n) => vec![n.to_string()],
+        }
+    }
+
+    fn to_string(&self, document: &Document) -> String {
+        self.strings(document).into_iter().next().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    Dot,
+    DoubleDot,
+    At,
+    Star,
+    Comma,
+    Eq,
+    NotEq,
+    OpenBracket,
+    CloseBracket,
+    OpenParen,
+    CloseParen,
+    Name(String),
+    Str(String),
+    Num(usize),
+}
+
+fn tokenize(xpath: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = xpath.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '/' if chars.next_if(|&(_, c)| c == '/').is_some() => Token::DoubleSlash,
+            '/' => Token::Slash,
+            '.' if chars.next_if(|&(_, c)| c == '.').is_some() => Token::DoubleDot,
+            '.' => Token::Dot,
+            '@' => Token::At,
+            '*' => Token::Star,
+            ',' => Token::Comma,
+            '=' => Token::Eq,
+            '!' if chars.next_if(|&(_, c)| c == '=').is_some() => Token::NotEq,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '"' | '\'' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, end)) if end == c => break,
+                        Some((_, c)) => s.push(c),
+                        None => return Err(format!("unterminated string at {i}")),
+                    }
+                }
+                Token::Str(s)
+            }
+            c if c.is_ascii_digit() => {
+                let mut n = c.to_digit(10).unwrap() as usize;
+                while let Some((_, d)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    n = n * 10 + d.to_digit(10).unwrap() as usize;
+                }
+                Token::Num(n)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some((_, c)) =
+                    chars.next_if(|&(_, c)| c.is_alphanumeric() || matches!(c, '_' | '-' | ':'))
+                {
+                    name.push(c);
+                }
+                Token::Name(name)
+            }
+            _ => return Err(format!("unexpected `{c}` at {i}")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_name(&self, expected: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(name)) if name == expected)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let eaten = self.peek() == Some(token);
+        if eaten {
+            self.pos += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(format!("expected {token:?}, found {:?}", self.peek()))
+        }
+    }
+
+    fn path(&mut self) -> Result<XPath, String> {
+        let absolute = matches!(self.peek(), Some(Token::Slash | Token::DoubleSlash));
+        let mut steps = Vec::new();
+        let mut descendant = false;
+        loop {
+            if self.eat(&Token::DoubleSlash) {
+                descendant = true;
+            } else if !self.eat(&Token::Slash) && !steps.is_empty() {
+                break;
+            }
+            steps.push(self.step(descendant)?);
+            descendant = false;
+        }
+        Ok(XPath { absolute, steps })
+    }
+
+    fn step(&mut self, descendant: bool) -> Result<Step, String> {
+        let test = match self.tokens.get(self.pos).cloned() {
+            Some(Token::Dot) => NodeTest::SelfNode,
+            Some(Token::DoubleDot) => NodeTest::Parent,
+            Some(Token::Star) => NodeTest::Element(None),
+            Some(Token::At) => {
+                self.pos += 1;
+                match self.peek() {
+                    Some(Token::Name(name)) => NodeTest::Attr(name.clone()),
+                    token => return Err(format!("expected an attribute name, found {token:?}")),
+                }
+            }
+            Some(Token::Name(name)) if self.tokens.get(self.pos + 1) == Some(&Token::OpenParen) => {
+                self.pos += 2;
+                if self.peek() != Some(&Token::CloseParen) {
+                    return Err(format!("unexpected arguments to `{name}()`"));
+                }
+                match &*name {
+                    "text" => NodeTest::Text,
+                    "node" => NodeTest::AnyNode,
+                    _ => return Err(format!("unsupported node test `{name}()`")),
+                }
+            }
+            Some(Token::Name(name)) => NodeTest::Element(Some(name.to_ascii_lowercase())),
+            token => return Err(format!("expected a step, found {token:?}")),
+        };
+        self.pos += 1;
+        let mut predicates = Vec::new();
+        while self.eat(&Token::OpenBracket) {
+            predicates.push(self.or()?);
+            self.expect(Token::CloseBracket)?;
+        }
+        Ok(Step { descendant, test, predicates })
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek_name("or") {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.comparison()?;
+        while self.peek_name("and") {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.primary()?;
+        let equal = match self.peek() {
+            Some(Token::Eq) => true,
+            Some(Token::NotEq) => false,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        Ok(Expr::Eq(Box::new(lhs), Box::new(self.primary()?), equal))
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::Str(s)) => {
+                self.pos += 1;
+                Ok(Expr::Str(s))
+            }
+            Some(Token::Num(n)) => {
+                self.pos += 1;
+                Ok(Expr::Num(n))
+            }
+            Some(Token::OpenParen) => {
+                self.pos += 1;
+                let expr = self.or()?;
+                self.expect(Token::CloseParen)?;
+                Ok(expr)
+            }
+            Some(Token::Name(name))
+                if self.tokens.get(self.pos + 1) == Some(&Token::OpenParen)
+                    && !matches!(&*name, "text" | "node") =>
+            {
+                self.pos += 2;
+                let mut args = Vec::new();
+                if !self.eat(&Token::CloseParen) {
+                    loop {
+                        args.push(self.or()?);
+                        if self.eat(&Token::CloseParen) {
+                            break;
+                        }
+                        self.expect(Token::Comma)?;
+                    }
+                }
+                let mut args = args.into_iter().map(Box::new);
+                let expr = match (&*name, args.len()) {
+                    ("not", 1) => Expr::Not(args.next().unwrap()),
+                    ("normalize-space", 1) => Expr::NormalizeSpace(args.next().unwrap()),
+                    ("normalize-space", 0) => Expr::NormalizeSpace(Box::new(Expr::Path(XPath {
+                        absolute: false,
+                        steps: vec![Step {
+                            descendant: false,
+                            test: NodeTest::SelfNode,
+                            predicates: Vec::new(),
+                        }],
+                    }))),
+                    ("contains", 2) => Expr::Contains(args.next().unwrap(), args.next().unwrap()),
+                    ("starts-with", 2) => {
+                        Expr::StartsWith(args.next().unwrap(), args.next().unwrap())
+                    }
+                    (name, n) => {
+                        return Err(format!("unsupported function `{name}` with {n} args"));
+                    }
+                };
+                Ok(expr)
+            }
+            _ => Ok(Expr::Path(self.path()?)),
+        }
+    }
+}
+
+impl XPath {
+    fn parse(xpath: &str) -> Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(xpath)?, pos: 0 };
+        let path = parser.path()?;
+        match parser.peek() {
+            None => Ok(path),
+            Some(token) => Err(format!("unexpected {token:?}")),
+        }
+    }
+
+    /// Evaluate the path with `context` as the context nodes, returning matches in document order.
+    fn select(&self, document: &Document, context: &[usize]) -> Vec<Match> {
+        let mut current: Vec<Match> = if self.absolute {
+            vec![Match::Node(Document::ROOT)]
+        } else {
+            context.iter().map(|&node| Match::Node(node)).collect()
+        };
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for m in &current {
+                let Match::Node(node) = *m else { continue };
+                let mut bases = Vec::new();
+                if step.descendant {
+                    document.descendants_or_self(node, &mut bases);
+                } else {
+                    bases.push(node);
+                }
+                for base in bases {
+                    let candidates = step.candidates(document, base);
+                    next.extend(step.filter(document, candidates));
+                }
+            }
+            next.sort_by_key(|m| match m {
+                Match::Node(node) => (*node, String::new()),
+                Match::Attr(node, name) => (*node, name.clone()),
+            });
+            next.dedup();
+            current = next;
+        }
+        current
+    }
+}
+
+impl Step {
+    /// The nodes reached from `node` by this step, before applying predicates.
+    fn candidates(&self, document: &Document, node: usize) -> Vec<Match> {
+        let children = || document.nodes[node].children.iter().copied();
+        match &self.test {
+            NodeTest::SelfNode => vec![Match::Node(node)],
+            NodeTest::Parent => document.nodes[node].parent.map(Match::Node).into_iter().collect(),
+            NodeTest::Element(name) => children()
+                .filter(|&child| match &document.nodes[child].kind {
+                    NodeKind::Element { name: actual, .. } => {
+                        name.is_none() || name.as_ref() == Some(actual)
+                    }
+                    _ => false,
+                })
+                .map(Match::Node)
+                .collect(),
+            NodeTest::Text => children()
+                .filter(|&child| matches!(document.nodes[child].kind, NodeKind::Text(_)))
+                .map(Match::Node)
+                .collect(),
+            NodeTest::AnyNode => children().map(Match::Node).collect(),
+            NodeTest::Attr(name) => document
+                .attr(node, name)
+                .map(|value| Match::Attr(node, value.to_string()))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn filter(&self, document: &Document, mut candidates: Vec<Match>) -> Vec<Match> {
+        for predicate in &self.predicates {
+            candidates = candidates
+                .iter()
+                .enumerate()
+                .filter(|(i, m)| match predicate.eval(document, m) {
+                    Value::Num(position) => position == i + 1,
+                    value => value.to_bool(),
+                })
+                .map(|(_, m)| m.clone())
+                .collect();
+        }
+        candidates
+    }
+}
+
+impl Expr {
+    fn eval(&self, document: &Document, context: &Match) -> Value {
+        let string = |expr: &Expr| expr.eval(document, context).to_string(document);
+        match self {
+            Expr::Or(lhs, rhs) => Value::Bool(
+                lhs.eval(document, context).to_bool() || rhs.eval(document, context).to_bool(),
+            ),
+            Expr::And(lhs, rhs) => Value::Bool(
+                lhs.eval(document, context).to_bool() && rhs.eval(document, context).to_bool(),
+            ),
+            Expr::Eq(lhs, rhs, equal) => {
+                let lhs = lhs.eval(document, context).strings(document);
+                let rhs = rhs.eval(document, context).strings(document);
+                Value::Bool(lhs.iter().any(|l| rhs.iter().any(|r| (l == r) == *equal)))
+            }
+            Expr::Not(expr) => Value::Bool(!expr.eval(document, context).to_bool()),
+            Expr::Contains(haystack, needle) => {
+                Value::Bool(string(haystack).contains(&string(needle)))
+            }
+            Expr::StartsWith(haystack, needle) => {
+                Value::Bool(string(haystack).starts_with(&string(needle)))
+            }
+            Expr::NormalizeSpace(expr) => Value::Str(normalize_whitespace(&string(expr))),
+            Expr::Path(path) => match context {
+                Match::Node(node) => Value::Matches(path.select(document, &[*node])),
+                Match::Attr(..) => Value::Matches(Vec::new()),
+            },
+            Expr::Str(s) => Value::Str(s.clone()),
+            Expr::Num(n) => Value::Num(*n),
+        }
+    }
+}
+
+/// Collapse runs of whitespace into single spaces and trim the ends, like htmldocck does.
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
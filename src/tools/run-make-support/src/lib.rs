@@ -4,9 +4,16 @@
 //! as `object` or `wasmparser`, they can be re-exported and be made available through this library.
 
 pub mod cc;
+pub mod command;
+pub mod diagnostics;
+pub mod diff;
+pub mod htmldocck;
+pub mod redirect_map;
 pub mod run;
 pub mod rustc;
 pub mod rustdoc;
+pub mod rustdoc_json;
+pub mod search_index;
 
 use std::env;
 use std::path::{Path, PathBuf};
@@ -14,12 +21,31 @@
 
 pub use object;
 pub use regex;
+pub use rustdoc_json_types;
+pub use serde_json;
 pub use wasmparser;
 
 pub use cc::{cc, extra_c_flags, extra_cxx_flags, Cc};
+pub use command::CompletedProcess;
+pub use diagnostics::{parse_diagnostics, Diagnostic, DiagnosticSpan};
+pub use htmldocck::{
+    assert_doc_file_exists, assert_doc_file_missing, assert_generated_files, assert_has_xpath,
+    assert_html_matches_snapshot, assert_item_doc_contains, assert_links_resolve, assert_no_xpath,
+    assert_no_xpath_text, assert_src_links, assert_static_root, assert_type_layout,
+    assert_xpath_attr, assert_xpath_count, assert_xpath_text,
+};
+pub use redirect_map::{read_redirect_map, RedirectMap};
 pub use run::{run, run_fail};
 pub use rustc::{aux_build, rustc, Rustc};
-pub use rustdoc::{bare_rustdoc, rustdoc, Rustdoc};
+pub use rustdoc::{
+    assert_asset_readable, assert_css_contains, bare_rustdoc, check_theme, document_all, pipe,
+    read_dep_info, resolved_asset, run_many, run_scrape_examples, rustdoc, rustdoc_version,
+    ColorChoice, CoverageReport, CrateNameError, CrateType, DoctestSummary, Emit, ErrorFormat,
+    ExternError, FileCoverage, JsonOpt, LibraryKind, LintLevel, OutputFormat, Rustdoc,
+    RustdocChild, RustdocPreset, RustdocVersion, TempDir, ThemeCheckResult,
+};
+pub use rustdoc_json::RustdocJson;
+pub use search_index::{read_search_index, SearchIndex};
 
 /// Path of `TMPDIR` (a temporary build directory, not under `/tmp`).
 pub fn tmp_dir() -> PathBuf {
@@ -137,18 +163,31 @@ pub fn set_host_rpath(cmd: &mut Command) {
 }
 
 /// Implement common helpers for command wrappers. This assumes that the command wrapper is a struct
-/// containing a `cmd: Command` field. The provided helpers are:
+/// containing a `cmd: Command` field, with a `command_output` method running it and returning its
+/// [`Output`]. The provided helpers are:
 ///
-/// 1. Generic argument acceptors: `arg` and `args` (delegated to [`Command`]). These are intended
-///    to be *fallback* argument acceptors, when specific helpers don't make sense. Prefer to add
-///    new specific helper methods over relying on these generic argument providers.
-/// 2. Environment manipulation methods: `env`, `env_remove` and `env_clear`: these delegate to
-///    methods of the same name on [`Command`].
-/// 3. Output and execution: `output`, `run` and `run_fail` are provided. `output` waits for the
-///    command to finish running and returns the process's [`Output`]. `run` and `run_fail` are
-///    higher-level convenience methods which waits for the command to finish running and assert
-///    that the command successfully ran or failed as expected. Prefer `run` and `run_fail` when
-///    possible.
+/// 1. Generic argument acceptors: `arg`, `args` and `arg_if` (delegated to [`Command`]). These are
+///    intended to be *fallback* argument acceptors, when specific helpers don't make sense. Prefer
+///    to add new specific helper methods over relying on these generic argument providers.
+/// 2. Environment manipulation methods: `env`, `env_remove`, `env_clear` and `current_dir`: these
+///    delegate to methods of the same name on [`Command`]. `env_clear` keeps the runtime library
+///    path of the host tools. [`Command`] doesn't tell whether its environment was cleared, so a
+///    wrapper which needs to know, to rebuild the command for example, can name a `bool` field that
+///    `env_clear` sets with `impl_common_helpers!(Wrapper, env_cleared: field)`.
+/// 3. Output and execution: `output`, `run`, `run_fail`, `run_fail_with_code`, `run_and_capture`,
+///    `run_unchecked` and `measured_run` are provided. `output` waits for the command to finish
+///    running and returns the process's [`Output`]. `run` and `run_fail` are higher-level
+///    convenience methods which waits for the command to finish running and assert that the
+///    command successfully ran or failed as expected, `run_fail_with_code` also checks the exact
+///    exit code. Prefer `run` and `run_fail` when possible. `run_and_capture` returns a
+///    [`CompletedProcess`] whatever the exit status, for tests which need to look at partial
+///    failures, and `run_unchecked` splits it in an `Ok` or `Err` depending on whether the command
+///    succeeded. `measured_run` is `run` also returning how long the command took, for tests
+///    guarding against slowdowns.
+/// 4. Debugging: `print_command` and `to_argv_string` render the command as a shell line.
+/// 5. Escape hatches: `command_mut` and `to_command` give access to the underlying [`Command`].
+///    They are the intended way for a test to use something the helpers don't support yet, which
+///    can then be added as a specific helper method.
 ///
 /// Example usage:
 ///
@@ -158,14 +197,19 @@ pub fn set_host_rpath(cmd: &mut Command) {
 /// crate::impl_common_helpers!(CommandWrapper);
 ///
 /// impl CommandWrapper {
+///     fn command_output(&mut self) -> Output {
+///         self.cmd.output().expect("failed to get output of finished process")
+///     }
+///
 ///     // ... additional specific helper methods
 /// }
 /// ```
 ///
 /// [`Command`]: ::std::process::Command
 /// [`Output`]: ::std::process::Output
+/// [`CompletedProcess`]: crate::CompletedProcess
 macro_rules! impl_common_helpers {
-    ($wrapper: ident) => {
+    ($wrapper: ident $(, env_cleared: $env_cleared: ident)?) => {
         impl $wrapper {
             /// Specify an environment variable.
             pub fn env<K, V>(&mut self, key: K, value: V) -> &mut Self
@@ -186,9 +230,24 @@ pub fn env_remove<K>(&mut self, key: K) -> &mut Self
                 self
             }
 
-            /// Clear all environmental variables.
-            pub fn env_var(&mut self) -> &mut Self {
+            /// Clear all environmental variables. The runtime library path needed to run the
+            /// host tools is set again afterwards, see [`set_host_rpath`].
+            ///
+            /// [`set_host_rpath`]: crate::set_host_rpath
+            pub fn env_clear(&mut self) -> &mut Self {
                 self.cmd.env_clear();
+                crate::set_host_rpath(&mut self.cmd);
+                $(self.$env_cleared = true;)?
+                self
+            }
+
+            /// Set the working directory of the command. Relative paths passed to the other
+            /// helpers are then resolved from it.
+            pub fn current_dir<P>(&mut self, dir: P) -> &mut Self
+            where
+                P: AsRef<::std::path::Path>,
+            {
+                self.cmd.current_dir(dir);
                 self
             }
 
@@ -206,14 +265,27 @@ pub fn arg<S>(&mut self, arg: S) -> &mut Self
             /// Generic command arguments provider. Prefer specific helper methods if possible.
             /// Note that for some executables, arguments might be platform specific. For C/C++
             /// compilers, arguments might be platform *and* compiler specific.
-            pub fn args<S>(&mut self, args: &[S]) -> &mut Self
+            pub fn args<I, S>(&mut self, args: I) -> &mut Self
             where
+                I: IntoIterator<Item = S>,
                 S: AsRef<::std::ffi::OsStr>,
             {
                 self.cmd.args(args);
                 self
             }
 
+            /// Generic command argument provider, only adding `arg` when `cond` holds. Prefer
+            /// specific helper methods if possible.
+            pub fn arg_if<S>(&mut self, cond: bool, arg: S) -> &mut Self
+            where
+                S: AsRef<::std::ffi::OsStr>,
+            {
+                if cond {
+                    self.cmd.arg(arg);
+                }
+                self
+            }
+
             /// Inspect what the underlying [`Command`][::std::process::Command] is up to the
             /// current construction.
             pub fn inspect<I>(&mut self, inspector: I) -> &mut Self
@@ -224,9 +296,34 @@ pub fn inspect<I>(&mut self, inspector: I) -> &mut Self
                 self
             }
 
+            /// Borrow the underlying [`Command`][::std::process::Command], to use what the
+            /// helpers don't cover yet. The helpers can still be used afterwards.
+            pub fn command_mut(&mut self) -> &mut ::std::process::Command {
+                &mut self.cmd
+            }
+
+            /// Take the underlying [`Command`][::std::process::Command], to use what the helpers
+            /// don't cover yet. Anything the wrapper applies when running the command, like a
+            /// configured stdin, is not part of it, and the files it wrote for the command may
+            /// be removed when it is dropped.
+            pub fn to_command(self) -> ::std::process::Command {
+                self.cmd
+            }
+
+            /// Print the program and arguments of the command to stderr, as a shell line.
+            pub fn print_command(&self) {
+                eprintln!("{}", self.to_argv_string());
+            }
+
+            /// The program and arguments of the command as a line which can be pasted in a
+            /// POSIX shell, quoting the arguments where needed. The environment is not included.
+            pub fn to_argv_string(&self) -> String {
+                crate::command::shell_line(&self.cmd)
+            }
+
             /// Get the [`Output`][::std::process::Output] of the finished process.
             pub fn output(&mut self) -> ::std::process::Output {
-                self.cmd.output().expect("failed to get output of finished process")
+                self.command_output()
             }
 
             /// Run the constructed command and assert that it is successfully run.
@@ -235,7 +332,7 @@ pub fn run(&mut self) -> ::std::process::Output {
                 let caller_location = ::std::panic::Location::caller();
                 let caller_line_number = caller_location.line();
 
-                let output = self.cmd.output().unwrap();
+                let output = self.command_output();
                 if !output.status.success() {
                     handle_failed_output(&self.cmd, output, caller_line_number);
                 }
@@ -248,12 +345,52 @@ pub fn run_fail(&mut self) -> ::std::process::Output {
                 let caller_location = ::std::panic::Location::caller();
                 let caller_line_number = caller_location.line();
 
-                let output = self.cmd.output().unwrap();
+                let output = self.command_output();
                 if output.status.success() {
                     handle_failed_output(&self.cmd, output, caller_line_number);
                 }
                 output
             }
+
+            /// Run the constructed command and capture its output, without asserting anything
+            /// about its exit status.
+            pub fn run_and_capture(&mut self) -> crate::CompletedProcess {
+                self.output().into()
+            }
+
+            /// Run the constructed command and assert that it exits with exactly `expected`,
+            /// returning its output for further assertions.
+            #[track_caller]
+            pub fn run_fail_with_code(&mut self, expected: i32) -> crate::CompletedProcess {
+                let process = self.run_and_capture();
+                process.assert_exit_code(expected);
+                process
+            }
+
+            /// Run the constructed command and return its output, as `Ok` if it succeeded and as
+            /// `Err` otherwise. Nothing is asserted, so the test decides how to handle both.
+            pub fn run_unchecked(
+                &mut self,
+            ) -> ::std::result::Result<crate::CompletedProcess, crate::CompletedProcess> {
+                let process = self.run_and_capture();
+                if process.status().success() { Ok(process) } else { Err(process) }
+            }
+
+            /// Run the constructed command and assert that it is successfully run, like `run`,
+            /// also returning how long it took to finish.
+            #[track_caller]
+            pub fn measured_run(&mut self) -> (crate::CompletedProcess, ::std::time::Duration) {
+                let caller_location = ::std::panic::Location::caller();
+                let caller_line_number = caller_location.line();
+
+                let start = ::std::time::Instant::now();
+                let output = self.command_output();
+                let elapsed = start.elapsed();
+                if !output.status.success() {
+                    handle_failed_output(&self.cmd, output, caller_line_number);
+                }
+                (output.into(), elapsed)
+            }
         }
     };
 }
@@ -0,0 +1,72 @@
+//! Reading the `redirect-map.json` rustdoc writes with `--generate-redirect-map`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Locate the `redirect-map.json` file rustdoc wrote in the crate directory of `out_dir`, and
+/// parse it. The keys are the paths of the pages which would have been a redirect, the values the
+/// paths of the pages they lead to, both relative to `out_dir`.
+#[track_caller]
+pub fn read_redirect_map<P: AsRef<Path>>(out_dir: P) -> BTreeMap<String, String> {
+    let out_dir = out_dir.as_ref();
+    let files: Vec<_> = fs::read_dir(out_dir)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", out_dir.display()))
+        .map(|entry| entry.unwrap().path().join("redirect-map.json"))
+        .filter(|path| path.is_file())
+        .collect();
+    let [file] = &files[..] else {
+        panic!("expected exactly one redirect map in `{}`, found {files:?}", out_dir.display());
+    };
+    let json = fs::read_to_string(file).unwrap();
+    serde_json::from_str(&json)
+        .unwrap_or_else(|err| panic!("failed to parse redirect map `{}`: {err}", file.display()))
+}
+
+/// The redirect map of a documentation directory, see [`read_redirect_map`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedirectMap {
+    redirects: BTreeMap<String, String>,
+}
+
+impl RedirectMap {
+    /// Locate and parse the redirect map of `out_dir`, like [`read_redirect_map`].
+    #[track_caller]
+    pub fn read<P: AsRef<Path>>(out_dir: P) -> Self {
+        Self::from(read_redirect_map(out_dir))
+    }
+
+    /// The page `from` redirects to, if any.
+    pub fn target_of(&self, from: &str) -> Option<&str> {
+        self.redirects.get(from).map(String::as_str)
+    }
+
+    /// Assert that the page `from` redirects to `to`.
+    #[track_caller]
+    pub fn assert_redirect(&self, from: &str, to: &str) {
+        match self.target_of(from) {
+            Some(target) if target == to => {}
+            Some(target) => panic!("`{from}` redirects to `{target}`, not to `{to}`"),
+            None => panic!("`{from}` has no redirect, expected one to `{to}`"),
+        }
+    }
+
+    /// Assert that the page `from` does not redirect anywhere.
+    #[track_caller]
+    pub fn assert_no_redirect(&self, from: &str) {
+        if let Some(target) = self.target_of(from) {
+            panic!("`{from}` redirects to `{target}`, expected no redirect");
+        }
+    }
+
+    /// All the redirects, from the old path to the new one.
+    pub fn redirects(&self) -> &BTreeMap<String, String> {
+        &self.redirects
+    }
+}
+
+impl From<BTreeMap<String, String>> for RedirectMap {
+    fn from(redirects: BTreeMap<String, String>) -> Self {
+        Self { redirects }
+    }
+}
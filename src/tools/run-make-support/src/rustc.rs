@@ -159,10 +159,14 @@ pub fn run_fail_assert_exit_code(&mut self, code: i32) -> Output {
         let caller_location = std::panic::Location::caller();
         let caller_line_number = caller_location.line();
 
-        let output = self.cmd.output().unwrap();
+        let output = self.command_output();
         if output.status.code().unwrap() != code {
             handle_failed_output(&self.cmd, output, caller_line_number);
         }
         output
     }
+
+    fn command_output(&mut self) -> Output {
+        self.cmd.output().expect("failed to get output of finished process")
+    }
 }
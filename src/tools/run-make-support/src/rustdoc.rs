@@ -1,46 +1,978 @@
+use std::collections::BTreeMap;
 use std::env;
-use std::path::Path;
-use std::process::{Command, Output};
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+#[cfg(unix)]
+use std::time::{Duration, Instant};
 
-use crate::{handle_failed_output, set_host_rpath};
+use regex::Regex;
+
+use crate::command::CompletedProcess;
+use crate::diagnostics::{parse_diagnostics, Diagnostic};
+use crate::diff::unified_diff;
+use crate::rustdoc_json::{assert_format_version, RustdocJson};
+use crate::{handle_failed_output, rustc, set_host_rpath, tmp_dir};
 
 /// Construct a plain `rustdoc` invocation with no flags set.
+#[track_caller]
 pub fn bare_rustdoc() -> Rustdoc {
     Rustdoc::bare()
 }
 
 /// Construct a new `rustdoc` invocation with `-L $(TARGET_RPATH_DIR)` set.
+#[track_caller]
 pub fn rustdoc() -> Rustdoc {
     Rustdoc::new()
 }
 
+/// The version of `$RUSTDOC`, read from `rustdoc --version --verbose` the first time it is needed.
+#[track_caller]
+pub fn rustdoc_version() -> RustdocVersion {
+    static VERSION: OnceLock<RustdocVersion> = OnceLock::new();
+    VERSION
+        .get_or_init(|| {
+            let output = bare_rustdoc().arg("--version").arg("--verbose").run();
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            RustdocVersion::parse(&stdout)
+                .unwrap_or_else(|| panic!("failed to parse the version of rustdoc:\n{stdout}"))
+        })
+        .clone()
+}
+
+/// Check the custom theme `css` against the default one with `rustdoc --check-theme` and return
+/// what it lacks. Panics if rustdoc couldn't check the theme, e.g. because it isn't valid CSS.
+#[track_caller]
+pub fn check_theme<P: AsRef<Path>>(css: P) -> ThemeCheckResult {
+    let caller_line_number = std::panic::Location::caller().line();
+    let mut doc = rustdoc();
+    doc.arg("--check-theme").arg(css.as_ref());
+    let output = doc.command_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let missing: Vec<_> = stdout
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = line
+                .strip_prefix("Missing CSS variable `")
+                .or_else(|| line.strip_prefix("Missing rule `"))?;
+            Some(rest.split('`').next().unwrap().to_owned())
+        })
+        .collect();
+    // A theme which can't be read or parsed fails without listing anything.
+    if output.status.success() != missing.is_empty() {
+        handle_failed_output(&doc.cmd, output, caller_line_number);
+    }
+    ThemeCheckResult { missing, extra: Vec::new() }
+}
+
+/// Document the library `lib` of crate `target_crate` together with examples scraped from the
+/// given `(crate name, path)` example binaries, writing the docs to `out_dir`.
+///
+/// This runs the whole scrape-examples workflow: `lib` is first built to metadata with `rustc`,
+/// each example is then scraped with `--scrape-examples-output-path` into
+/// `$TMPDIR/{name}.calls`, and finally `lib` is documented with one `--with-examples` per
+/// example.
+#[track_caller]
+pub fn run_scrape_examples<P, Q, R>(target_crate: &str, lib: P, examples: &[(&str, Q)], out_dir: R)
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    R: AsRef<Path>,
+{
+    let lib = lib.as_ref();
+    let out_dir = out_dir.as_ref();
+
+    rustc()
+        .input(lib)
+        .arg("--crate-name")
+        .arg(target_crate)
+        .crate_type("lib")
+        .emit("metadata")
+        .run();
+    let rmeta = tmp_dir().join(format!("lib{target_crate}.rmeta"));
+
+    let mut calls = Vec::new();
+    for (name, example) in examples {
+        let output = tmp_dir().join(format!("{name}.calls"));
+        rustdoc()
+            .input(example)
+            .crate_name(name)
+            .crate_type("bin")
+            .out_dir(out_dir)
+            .extern_(target_crate, &rmeta)
+            .scrape_examples_output_path(&output)
+            .scrape_examples_target_crate(target_crate)
+            .run();
+        calls.push(output);
+    }
+
+    let mut doc = rustdoc();
+    doc.input(lib).crate_name(target_crate).crate_type("lib").out_dir(out_dir);
+    for output in &calls {
+        doc.with_examples(output);
+    }
+    doc.run();
+}
+
+/// Run `producer` with its standard output connected to the standard input of `rustdoc`, both
+/// running at the same time, and return the output of rustdoc. Like [`Rustdoc::run`], the test
+/// fails if either of them does.
+#[track_caller]
+pub fn pipe(producer: &mut Command, rustdoc: &mut Rustdoc) -> CompletedProcess {
+    let caller_line_number = std::panic::Location::caller().line();
+    let mut producer_child = producer
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|err| panic!("failed to spawn {producer:?}: {err}"));
+    let producer_stderr = read_in_background(producer_child.stderr.take().unwrap());
+    let producer_stdout = producer_child.stdout.take().unwrap();
+    let output = rustdoc.spawn_with_stdin(Some(producer_stdout.into()), false).wait_with_output();
+
+    let status = producer_child.wait().unwrap();
+    if !status.success() {
+        let stderr = producer_stderr.join().unwrap();
+        handle_failed_output(
+            producer,
+            Output { status, stdout: Vec::new(), stderr },
+            caller_line_number,
+        );
+    }
+    if !output.status.success() {
+        handle_failed_output(&rustdoc.cmd, output, caller_line_number);
+    }
+    output.into()
+}
+
+/// Run independent `invocations` at the same time, as many at once as there are available
+/// cores, and return their outputs in the same order. Nothing is asserted about their exit
+/// statuses. Each invocation must write to its own output directory, so that they don't
+/// overwrite each other's files.
+#[track_caller]
+pub fn run_many(invocations: Vec<Rustdoc>) -> Vec<CompletedProcess> {
+    let mut out_dirs = Vec::new();
+    for rustdoc in &invocations {
+        let out_dir = rustdoc.configured_out_dir();
+        assert!(
+            !out_dirs.contains(&out_dir),
+            "several invocations passed to `run_many` write to `{}`",
+            out_dir.display()
+        );
+        out_dirs.push(out_dir);
+    }
+
+    let count = invocations.len();
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(count);
+    let queue = Mutex::new(invocations.into_iter().enumerate());
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        // The lock is only held to take the next invocation, not while running it.
+                        let next = queue.lock().unwrap().next();
+                        let Some((i, mut rustdoc)) = next else { break };
+                        results.push((i, rustdoc.run_and_capture()));
+                    }
+                    results
+                })
+            })
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, process)| process).collect()
+}
+
+/// Document each `.rs` file of `dir`, in sorted order, with a clone of `base` which writes to
+/// `{out_dir}/{file stem}`, where `out_dir` is the output directory configured on `base`. The
+/// invocations are run like with [`run_many`], and their outputs returned in the same order.
+#[track_caller]
+pub fn document_all<P: AsRef<Path>>(base: Rustdoc, dir: P) -> Vec<CompletedProcess> {
+    let dir = dir.as_ref();
+    let mut inputs: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    inputs.sort();
+    let out_dir = base.configured_out_dir();
+    let base = base.without_out_dir();
+    let invocations = inputs
+        .iter()
+        .map(|input| {
+            let mut rustdoc = base.clone();
+            rustdoc.input(input).out_dir(out_dir.join(input.file_stem().unwrap()));
+            rustdoc
+        })
+        .collect();
+    run_many(invocations)
+}
+
+/// Find the file rustdoc generated in `out_dir` for the asset `base`, like `search-index.js` or
+/// `rustdoc.css`, when documenting with the resource suffix `suffix`.
+///
+/// The files specific to an invocation, like `search-index.js` or `{crate}/sidebar-items.js`, get
+/// the suffix before their extension. The static files in `static.files` get a content hash
+/// instead, like `static.files/rustdoc-{hash}.css`, and are looked up as such when there is no
+/// suffixed file. Panics if neither exists.
+#[track_caller]
+pub fn resolved_asset<P: AsRef<Path>>(out_dir: P, base: &str, suffix: &str) -> PathBuf {
+    let out_dir = out_dir.as_ref();
+    let base = Path::new(base);
+    let file_name = base.file_name().unwrap().to_str().unwrap();
+    // The extension includes its dot, and is everything after the first one as in `.ttf.woff2`.
+    let (stem, extension) =
+        file_name.find('.').map_or((file_name, ""), |dot| file_name.split_at(dot));
+
+    let suffixed = out_dir.join(base).with_file_name(format!("{stem}{suffix}{extension}"));
+    if suffixed.exists() {
+        return suffixed;
+    }
+    let static_files = out_dir.join("static.files");
+    let hashed_name =
+        Regex::new(&format!("^{}-[0-9a-f]+{}$", regex::escape(stem), regex::escape(extension)))
+            .unwrap();
+    let entries = fs::read_dir(&static_files).into_iter().flatten();
+    let mut hashed = entries.map(|entry| entry.unwrap().path());
+    hashed
+        .find(|path| hashed_name.is_match(&path.file_name().unwrap().to_string_lossy()))
+        .unwrap_or_else(|| {
+            panic!(
+                "neither `{}` nor a hashed `{file_name}` in `{}` exist",
+                suffixed.display(),
+                static_files.display()
+            )
+        })
+}
+
+/// Assert that the `theme{suffix}.css` rustdoc wrote to `out_dir`, whatever its resource suffix,
+/// contains `needle`. That is where the rules passed with [`Rustdoc::extend_css`] end up.
+#[track_caller]
+pub fn assert_css_contains<P: AsRef<Path>>(out_dir: P, needle: &str) {
+    let out_dir = out_dir.as_ref();
+    let files: Vec<_> = fs::read_dir(out_dir)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", out_dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("theme") && name.ends_with(".css")
+        })
+        .collect();
+    let [file] = &files[..] else {
+        panic!("expected exactly one theme CSS in `{}`, found {files:?}", out_dir.display());
+    };
+    let css = fs::read_to_string(file).unwrap();
+    assert!(css.contains(needle), "`{}` does not contain `{needle}`", file.display());
+}
+
+/// Assert that the JS or CSS file `asset`, like one found with [`resolved_asset`], looks like the
+/// readable source rather than minified output: it spans several lines and none of them is longer
+/// than 200 characters, where the sources themselves are held to 100 by tidy.
+#[track_caller]
+pub fn assert_asset_readable<P: AsRef<Path>>(asset: P) {
+    let asset = asset.as_ref();
+    let text = fs::read_to_string(asset)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", asset.display()));
+    assert!(text.lines().count() > 1, "`{}` is a single line, it looks minified", asset.display());
+    if let Some((i, line)) = text.lines().enumerate().find(|(_, line)| line.len() > 200) {
+        panic!(
+            "line {} of `{}` is {} characters long, it looks minified",
+            i + 1,
+            asset.display(),
+            line.len()
+        );
+    }
+}
+
+/// Read a makefile-style dependency file, like the ones `rustc --emit=dep-info` writes, and return
+/// the input files it lists, in order and without duplicates. The escaped spaces of the paths are
+/// unescaped, and the `# env-dep:` comments are skipped.
+#[track_caller]
+pub fn read_dep_info<P: AsRef<Path>>(path: P) -> Vec<PathBuf> {
+    let path = path.as_ref();
+    let dep_info = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", path.display()));
+    let mut inputs = Vec::new();
+    for line in dep_info.lines().filter(|line| !line.starts_with('#')) {
+        // The targets come first, and `: ` can't be in a Windows drive prefix like `C:\`.
+        let Some((_, deps_line)) = line.split_once(": ") else { continue };
+        // Paths can't contain NUL, so it stands for the escaped spaces while splitting.
+        let escaped = deps_line.replace("\\ ", "\0");
+        let deps = escaped.split(' ').map(|dep| dep.replace('\0', " "));
+        for dep in deps.filter(|dep| !dep.is_empty()).map(PathBuf::from) {
+            if !inputs.contains(&dep) {
+                inputs.push(dep);
+            }
+        }
+    }
+    inputs
+}
+
+/// Error returned by [`Rustdoc::try_extern_`] when the crate name contains a character which is
+/// not allowed there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternError {
+    pub crate_name: String,
+    /// The first disallowed character found in `crate_name`.
+    pub character: char,
+}
+
+impl std::fmt::Display for ExternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = if self.character.is_whitespace() { "whitespace" } else { "a path separator" };
+        write!(
+            f,
+            "crate name `{}` cannot contain whitespace or path separators, found {what} ({:?})",
+            self.crate_name, self.character
+        )
+    }
+}
+
+impl std::error::Error for ExternError {}
+
+fn check_extern_crate_name(crate_name: &str) -> Result<(), ExternError> {
+    match crate_name.chars().find(|&c| c.is_whitespace() || c == '\\' || c == '/') {
+        Some(character) => Err(ExternError { crate_name: crate_name.to_string(), character }),
+        None => Ok(()),
+    }
+}
+
+/// Error returned by [`Rustdoc::try_crate_name`] when the name is not a valid crate name, which
+/// is made of letters, digits and underscores and doesn't start with a digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateNameError {
+    pub crate_name: String,
+    /// The first disallowed character found in `crate_name`, or `None` if it is empty.
+    pub character: Option<char>,
+}
+
+impl std::fmt::Display for CrateNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a valid crate name: ", self.crate_name)?;
+        match self.character {
+            None => write!(f, "it is empty"),
+            Some(c) if c.is_ascii_digit() => write!(f, "it starts with a digit"),
+            Some('-') => write!(f, "it contains `-`, use `_` instead"),
+            Some(c) => write!(f, "it contains {c:?}, only letters, digits and `_` are allowed"),
+        }
+    }
+}
+
+impl std::error::Error for CrateNameError {}
+
+fn check_crate_name(crate_name: &str) -> Result<(), CrateNameError> {
+    let character = match crate_name.chars().next() {
+        None => None,
+        Some(first) if first.is_ascii_digit() => Some(first),
+        Some(_) => match crate_name.chars().find(|&c| !c.is_alphanumeric() && c != '_') {
+            Some(character) => Some(character),
+            None => return Ok(()),
+        },
+    };
+    Err(CrateNameError { crate_name: crate_name.to_string(), character })
+}
+
+/// Assert that `url`, passed to `flag`, is an absolute `http` or `https` URL with a host.
+#[track_caller]
+fn check_playground_url(flag: &str, url: &str) {
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap());
+    assert!(
+        host.is_some_and(|host| !host.is_empty()) && !url.contains(char::is_whitespace),
+        "URL passed to `{flag}` must be an absolute `http(s)` URL: `{url}`"
+    );
+}
+
+/// Join `values` with commas, keeping only the first occurrence of each value.
+fn join_deduplicated<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    let mut unique = Vec::new();
+    for value in values {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    unique.join(",")
+}
+
+/// Output format accepted by `rustdoc --output-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Html,
+    Json,
+}
+
+impl OutputFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Html => "html",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+/// Crate type accepted by `rustdoc --crate-type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrateType {
+    Bin,
+    Lib,
+    Rlib,
+    Dylib,
+    Cdylib,
+    Staticlib,
+    ProcMacro,
+}
+
+impl CrateType {
+    fn as_str(self) -> &'static str {
+        match self {
+            CrateType::Bin => "bin",
+            CrateType::Lib => "lib",
+            CrateType::Rlib => "rlib",
+            CrateType::Dylib => "dylib",
+            CrateType::Cdylib => "cdylib",
+            CrateType::Staticlib => "staticlib",
+            CrateType::ProcMacro => "proc-macro",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "bin" => CrateType::Bin,
+            "lib" => CrateType::Lib,
+            "rlib" => CrateType::Rlib,
+            "dylib" => CrateType::Dylib,
+            "cdylib" => CrateType::Cdylib,
+            "staticlib" => CrateType::Staticlib,
+            "proc-macro" => CrateType::ProcMacro,
+            _ => return None,
+        })
+    }
+}
+
+/// Lint level, as set by `-A`/`-W`/`-D`/`-F` or `--cap-lints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    fn short_flag(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "-A",
+            LintLevel::Warn => "-W",
+            LintLevel::Deny => "-D",
+            LintLevel::Forbid => "-F",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+            LintLevel::Forbid => "forbid",
+        }
+    }
+}
+
+/// Counts from the libtest `test result:` summary line printed by `rustdoc --test`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DoctestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub measured: usize,
+    pub filtered_out: usize,
+}
+
+impl DoctestSummary {
+    /// Parse the summary out of libtest's stdout, if there is one.
+    fn parse(stdout: &str) -> Option<Self> {
+        let line = stdout.lines().find_map(|line| line.trim().strip_prefix("test result: "))?;
+        // The line looks like `ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; ...`.
+        let (_, counts) = line.split_once(". ")?;
+        let mut summary = DoctestSummary::default();
+        for count in counts.split("; ") {
+            let Some((n, what)) = count.split_once(' ') else { continue };
+            let Ok(n) = n.parse() else { continue };
+            match what {
+                "passed" => summary.passed = n,
+                "failed" => summary.failed = n,
+                "ignored" => summary.ignored = n,
+                "measured" => summary.measured = n,
+                "filtered out" => summary.filtered_out = n,
+                _ => {}
+            }
+        }
+        Some(summary)
+    }
+}
+
+/// The version of rustdoc, see [`rustdoc_version`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustdocVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// `stable`, `beta`, `nightly` or `dev`.
+    pub channel: String,
+    /// `None` for a rustdoc built outside of a git checkout.
+    pub commit_hash: Option<String>,
+}
+
+impl RustdocVersion {
+    /// Parse the output of `rustdoc --version --verbose`, whose `release:` line looks like
+    /// `1.79.0-nightly`, or `1.78.0` on stable.
+    fn parse(stdout: &str) -> Option<Self> {
+        let field = |name: &str| {
+            stdout.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+        };
+        let release = field("release")?;
+        let (version, channel) = release.split_once('-').unwrap_or((release, "stable"));
+        let mut numbers = version.split('.').map(str::parse);
+        let (major, minor, patch) =
+            (numbers.next()?.ok()?, numbers.next()?.ok()?, numbers.next()?.ok()?);
+        // Betas are released as `beta.N`.
+        let channel = channel.split('.').next().unwrap().to_owned();
+        let commit_hash = field("commit-hash").filter(|&hash| hash != "unknown").map(str::to_owned);
+        Some(RustdocVersion { major, minor, patch, channel, commit_hash })
+    }
+}
+
+/// What a custom theme lacks compared to the default one, see [`check_theme`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ThemeCheckResult {
+    /// The missing CSS variables of `:root`, like `--main-color`, and the missing selectors, in
+    /// the order rustdoc reported them. Empty if the theme is complete.
+    pub missing: Vec<String>,
+    /// The rules the theme has but the default one doesn't. `--check-theme` only reports what is
+    /// missing, since a theme may style more than rustdoc needs, so this is always empty.
+    pub extra: Vec<String>,
+}
+
+/// Documentation coverage of a single file, or of the whole crate, as reported by
+/// `rustdoc --show-coverage`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FileCoverage {
+    pub file: String,
+    /// Number of items which are documented or should be.
+    pub total: u64,
+    /// Number of documented items.
+    pub with_docs: u64,
+    /// Number of items which have or should have a code example.
+    pub total_examples: u64,
+    /// Number of items with a code example.
+    pub with_examples: u64,
+}
+
+impl FileCoverage {
+    /// Percentage of documented items, if there are any items to document.
+    pub fn percentage(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.with_docs as f64 * 100.0 / self.total as f64)
+    }
+
+    /// Percentage of items with a code example, if there are any which should have one.
+    pub fn examples_percentage(&self) -> Option<f64> {
+        (self.total_examples > 0)
+            .then(|| self.with_examples as f64 * 100.0 / self.total_examples as f64)
+    }
+}
+
+/// The documentation coverage report produced by `rustdoc --show-coverage --output-format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CoverageReport {
+    /// Per-file coverage, sorted by file name.
+    pub files: Vec<FileCoverage>,
+    /// Coverage summed over all files, with `file` set to `"Total"`.
+    pub total: FileCoverage,
+}
+
+impl CoverageReport {
+    /// Parse the JSON coverage report, which maps each file name to its item counts.
+    fn parse(json: &str) -> Option<Self> {
+        let json: serde_json::Value = serde_json::from_str(json).ok()?;
+        let count = |counts: &serde_json::Value, key: &str| counts.get(key)?.as_u64();
+        let mut report = CoverageReport::default();
+        report.total.file = "Total".to_string();
+        for (file, counts) in json.as_object()? {
+            let file = FileCoverage {
+                file: file.clone(),
+                total: count(counts, "total")?,
+                with_docs: count(counts, "with_docs")?,
+                total_examples: count(counts, "total_examples")?,
+                with_examples: count(counts, "with_examples")?,
+            };
+            report.total.total += file.total;
+            report.total.with_docs += file.with_docs;
+            report.total.total_examples += file.total_examples;
+            report.total.with_examples += file.with_examples;
+            report.files.push(file);
+        }
+        report.files.sort_by(|a, b| a.file.cmp(&b.file));
+        Some(report)
+    }
+}
+
+/// Diagnostic output format accepted by `rustdoc --error-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Human,
+    Json,
+    Short,
+}
+
+impl ErrorFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorFormat::Human => "human",
+            ErrorFormat::Json => "json",
+            ErrorFormat::Short => "short",
+        }
+    }
+}
+
+/// Option of the JSON diagnostics, as passed to `rustdoc --json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonOpt {
+    /// Render the `rendered` field of the diagnostics in the short format.
+    DiagnosticShort,
+    /// Color the `rendered` field of the diagnostics with ANSI escape codes.
+    DiagnosticRenderedAnsi,
+    /// Emit a notification for each artifact written.
+    Artifacts,
+    /// Report the unused externs.
+    UnusedExterns,
+    /// Report the unused externs without turning them into errors.
+    UnusedExternsSilent,
+    /// Emit the future incompatibility reports.
+    FutureIncompat,
+}
+
+impl JsonOpt {
+    fn as_str(self) -> &'static str {
+        match self {
+            JsonOpt::DiagnosticShort => "diagnostic-short",
+            JsonOpt::DiagnosticRenderedAnsi => "diagnostic-rendered-ansi",
+            JsonOpt::Artifacts => "artifacts",
+            JsonOpt::UnusedExterns => "unused-externs",
+            JsonOpt::UnusedExternsSilent => "unused-externs-silent",
+            JsonOpt::FutureIncompat => "future-incompat",
+        }
+    }
+}
+
+/// Coloring of diagnostics, as passed to `rustdoc --color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn as_str(self) -> &'static str {
+        match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        }
+    }
+}
+
+/// Kind of a library search path, as in `-L kind=path`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryKind {
+    Dependency,
+    Crate,
+    Native,
+    Framework,
+    All,
+}
+
+impl LibraryKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LibraryKind::Dependency => "dependency",
+            LibraryKind::Crate => "crate",
+            LibraryKind::Native => "native",
+            LibraryKind::Framework => "framework",
+            LibraryKind::All => "all",
+        }
+    }
+}
+
+/// Kind of output accepted by `rustdoc --emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit {
+    UnversionedSharedResources,
+    ToolchainSharedResources,
+    InvocationSpecific,
+}
+
+impl Emit {
+    fn as_str(self) -> &'static str {
+        match self {
+            Emit::UnversionedSharedResources => "unversioned-shared-resources",
+            Emit::ToolchainSharedResources => "toolchain-shared-resources",
+            Emit::InvocationSpecific => "invocation-specific",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Rustdoc {
     cmd: Command,
+    stdin: Option<Stdin>,
+    /// Whether stderr goes to the same place as stdout, see [`Rustdoc::capture_combined`].
+    combined: bool,
+    /// Whether the arguments are passed through an arg file, see [`Rustdoc::use_arg_file`].
+    via_arg_file: bool,
+    /// Whether the `--extern` paths are canonicalized, see [`Rustdoc::canonicalize_externs`].
+    canonical_externs: bool,
+    /// Whether the environment was cleared by `env_clear`, to clear it again in the commands
+    /// built from this one.
+    env_cleared: bool,
+    /// The features given to [`Rustdoc::target_feature`], passed in a single `-C target-feature`
+    /// when rustdoc is spawned.
+    target_features: Vec<String>,
+    /// The inputs written by [`Rustdoc::input_str`], shared with the clones and removed once the
+    /// last of them is dropped.
+    temp_inputs: Vec<Arc<TempDir>>,
+}
+
+/// A directory in `TMPDIR` which is removed once dropped, like the output directory of
+/// [`Rustdoc::run_in_tempdir`]. A directory the test configured itself is kept.
+#[derive(Debug)]
+pub struct TempDir {
+    dir: PathBuf,
+    remove: bool,
+}
+
+impl TempDir {
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if self.remove {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+/// What the standard input of rustdoc is fed from.
+#[derive(Debug, Clone)]
+enum Stdin {
+    Bytes(Box<[u8]>),
+    /// Opened when rustdoc is spawned.
+    File(PathBuf),
+}
+
+crate::impl_common_helpers!(Rustdoc, env_cleared: env_cleared);
+
+/// [`Command`] can't be cloned, so the clone is a new command built from the program, arguments,
+/// environment and working directory of the original. An environment cleared by `env_clear` is
+/// cleared in the clone too, before the variables set afterwards.
+impl Clone for Rustdoc {
+    fn clone(&self) -> Self {
+        Self {
+            cmd: self.copied(),
+            stdin: self.stdin.clone(),
+            combined: self.combined,
+            via_arg_file: self.via_arg_file,
+            canonical_externs: self.canonical_externs,
+            env_cleared: self.env_cleared,
+            target_features: self.target_features.clone(),
+            temp_inputs: self.temp_inputs.clone(),
+        }
+    }
+}
+
+/// A configuration shared by several invocations, like the edition, the externs or the output
+/// directory. It is set up with the methods of [`Rustdoc`], but never run itself:
+/// [`Rustdoc::from_preset`] starts each invocation from a copy of it.
+#[derive(Debug, Clone)]
+pub struct RustdocPreset {
+    base: Rustdoc,
+}
+
+impl RustdocPreset {
+    /// Construct an empty preset, starting like [`Rustdoc::new`].
+    #[track_caller]
+    pub fn new() -> Self {
+        Self { base: Rustdoc::new() }
+    }
+}
+
+impl Default for RustdocPreset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for RustdocPreset {
+    type Target = Rustdoc;
+
+    fn deref(&self) -> &Rustdoc {
+        &self.base
+    }
+}
+
+impl DerefMut for RustdocPreset {
+    fn deref_mut(&mut self) -> &mut Rustdoc {
+        &mut self.base
+    }
 }
 
-crate::impl_common_helpers!(Rustdoc);
+/// The environment variables all invocations rely on, which compiletest sets.
+const COMMON_ENV_VARS: &[&str] = &["TMPDIR", "HOST_RPATH_DIR", "LD_LIB_PATH_ENVVAR"];
+
+/// Assert that [`COMMON_ENV_VARS`] and `extra` are all set and not empty, listing the missing
+/// ones otherwise, rather than failing later in some confusing way.
+#[track_caller]
+fn assert_env_vars(extra: &[&str]) {
+    let missing: Vec<_> = COMMON_ENV_VARS
+        .iter()
+        .chain(extra)
+        .filter(|var| env::var_os(var).unwrap_or_default().is_empty())
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "rustdoc can't be set up without the environment variables {missing:?}, \
+        is this test run by compiletest?"
+    );
+}
 
 fn setup_common() -> Command {
-    let rustdoc = env::var("RUSTDOC").unwrap();
-    let mut cmd = Command::new(rustdoc);
+    setup_with_rustdoc(env::var("RUSTDOC").unwrap())
+}
+
+fn setup_with_rustdoc<P: AsRef<Path>>(rustdoc: P) -> Command {
+    let mut cmd = Command::new(rustdoc.as_ref());
     set_host_rpath(&mut cmd);
     cmd
 }
 
+/// Pass `-L $(TARGET_RPATH_DIR)`, as all the non-bare invocations do.
+fn add_target_rpath(cmd: &mut Command) {
+    let target_rpath_dir = env::var_os("TARGET_RPATH_DIR").unwrap();
+    cmd.arg(format!("-L{}", target_rpath_dir.to_string_lossy()));
+}
+
+/// A running rustdoc, started by [`Rustdoc::spawn`].
+#[derive(Debug)]
+pub struct RustdocChild {
+    child: Child,
+    /// The file both outputs go to with [`Rustdoc::capture_combined`].
+    capture: Option<PathBuf>,
+    /// The thread writing the bytes given to [`Rustdoc::stdin`] to the standard input of rustdoc.
+    stdin_writer: Option<thread::JoinHandle<()>>,
+}
+
+impl RustdocChild {
+    /// Take the standard input of rustdoc, which is closed once it is dropped. Panics if it was
+    /// already taken, or if it wasn't piped because a standard input was configured.
+    #[track_caller]
+    pub fn stdin(&mut self) -> ChildStdin {
+        self.child.stdin.take().expect("the standard input of rustdoc isn't available")
+    }
+
+    /// Kill rustdoc, if it is still running.
+    #[track_caller]
+    pub fn kill(&mut self) {
+        self.child.kill().unwrap_or_else(|err| panic!("failed to kill rustdoc: {err}"));
+    }
+
+    /// Wait for rustdoc to exit and return its output, whether it succeeded or not.
+    #[track_caller]
+    pub fn wait(self) -> CompletedProcess {
+        CompletedProcess::new(self.wait_with_output(), false)
+    }
+
+    #[track_caller]
+    fn wait_with_output(self) -> Output {
+        let mut output =
+            self.child.wait_with_output().expect("failed to get output of finished process");
+        if let Some(writer) = self.stdin_writer {
+            writer.join().unwrap();
+        }
+        read_combined(&mut output, self.capture);
+        output
+    }
+}
+
 impl Rustdoc {
-    /// Construct a bare `rustdoc` invocation.
+    fn from_command(cmd: Command) -> Self {
+        Self {
+            cmd,
+            stdin: None,
+            combined: false,
+            via_arg_file: false,
+            canonical_externs: false,
+            env_cleared: false,
+            target_features: Vec::new(),
+            temp_inputs: Vec::new(),
+        }
+    }
+
+    /// Construct a bare `rustdoc` invocation. Panics if the environment variables it needs, like
+    /// `RUSTDOC`, aren't set.
+    #[track_caller]
     pub fn bare() -> Self {
+        assert_env_vars(&["RUSTDOC"]);
         let cmd = setup_common();
-        Self { cmd }
+        Self::from_command(cmd)
     }
 
-    /// Construct a `rustdoc` invocation with `-L $(TARGET_RPATH_DIR)` set.
+    /// Construct a `rustdoc` invocation with `-L $(TARGET_RPATH_DIR)` set. Panics if the
+    /// environment variables it needs, like `RUSTDOC` and `TARGET_RPATH_DIR`, aren't set.
+    #[track_caller]
     pub fn new() -> Self {
+        assert_env_vars(&["RUSTDOC", "TARGET_RPATH_DIR"]);
         let mut cmd = setup_common();
-        let target_rpath_dir = env::var_os("TARGET_RPATH_DIR").unwrap();
-        cmd.arg(format!("-L{}", target_rpath_dir.to_string_lossy()));
-        Self { cmd }
+        add_target_rpath(&mut cmd);
+        Self::from_command(cmd)
+    }
+
+    /// Construct a bare invocation of the rustdoc binary at `path` instead of `$(RUSTDOC)`, to
+    /// compare with a reference build for example.
+    #[track_caller]
+    pub fn bare_with_rustdoc<P: AsRef<Path>>(path: P) -> Self {
+        assert_env_vars(&[]);
+        Self::from_command(setup_with_rustdoc(path))
+    }
+
+    /// Construct an invocation of the rustdoc binary at `path` instead of `$(RUSTDOC)`, with
+    /// `-L $(TARGET_RPATH_DIR)` set like [`Rustdoc::new`].
+    #[track_caller]
+    pub fn with_rustdoc<P: AsRef<Path>>(path: P) -> Self {
+        assert_env_vars(&["TARGET_RPATH_DIR"]);
+        let mut cmd = setup_with_rustdoc(path);
+        add_target_rpath(&mut cmd);
+        Self::from_command(cmd)
+    }
+
+    /// Construct an invocation with the configuration of `preset`. The preset is copied like
+    /// with [`Clone`], so changing the invocation doesn't change the preset.
+    pub fn from_preset(preset: &RustdocPreset) -> Self {
+        preset.base.clone()
     }
 
     /// Specify path to the input file.
@@ -49,28 +981,1299 @@ pub fn input<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
         self
     }
 
-    /// Specify output directory.
+    /// Document the crate `crate_name` made of `source`, without a fixture file. The source is
+    /// written to `{crate_name}.rs` in a directory of its own in `TMPDIR`, which is removed once
+    /// this invocation and its clones are dropped. `--crate-name` is set too, so `crate_name` must
+    /// be valid, see [`Rustdoc::crate_name`].
+    #[track_caller]
+    pub fn input_str(&mut self, crate_name: &str, source: &str) -> &mut Self {
+        static INPUTS: AtomicUsize = AtomicUsize::new(0);
+        let dir =
+            tmp_dir().join(format!("rustdoc-input-{}", INPUTS.fetch_add(1, Ordering::Relaxed)));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join(format!("{crate_name}.rs"));
+        fs::write(&input, source)
+            .unwrap_or_else(|err| panic!("failed to write `{}`: {err}", input.display()));
+        self.temp_inputs.push(Arc::new(TempDir { dir, remove: true }));
+        self.input(input).crate_name(crate_name)
+    }
+
+    /// Render the standalone markdown file `md` to HTML instead of documenting a crate, the
+    /// page is written to `{stem}.html` in the output directory. rustdoc picks this mode from the
+    /// extension, so `md` must end in `.md` or `.markdown`.
+    #[track_caller]
+    pub fn markdown<P: AsRef<Path>>(&mut self, md: P) -> &mut Self {
+        let md = md.as_ref();
+        assert!(
+            matches!(md.extension().and_then(OsStr::to_str), Some("md" | "markdown")),
+            "markdown input must have a `.md` or `.markdown` extension: `{}`",
+            md.display()
+        );
+        self.cmd.arg(md);
+        self
+    }
+
+    /// Don't add a table of contents to the rendered markdown file (`--markdown-no-toc`).
+    pub fn markdown_no_toc(&mut self) -> &mut Self {
+        self.cmd.arg("--markdown-no-toc");
+        self
+    }
+
+    /// Link the stylesheet `css` from the rendered markdown file (`--markdown-css`). The path is
+    /// used as is in the `<link>`, so it is relative to the output directory. Can be called
+    /// multiple times.
+    pub fn markdown_css<P: AsRef<Path>>(&mut self, css: P) -> &mut Self {
+        self.cmd.arg("--markdown-css").arg(css.as_ref());
+        self
+    }
+
+    /// Send the standard error of rustdoc to the same place as its standard output, so that
+    /// [`CompletedProcess::combined_utf8`] returns both in the order they were written. The two
+    /// can't be told apart anymore: everything is in the standard output, and the standard error
+    /// is empty.
+    pub fn capture_combined(&mut self) -> &mut Self {
+        self.combined = true;
+        self
+    }
+
+    /// Feed `input` to the standard input of rustdoc.
+    pub fn stdin<I: AsRef<[u8]>>(&mut self, input: I) -> &mut Self {
+        self.stdin = Some(Stdin::Bytes(input.as_ref().into()));
+        self
+    }
+
+    /// Document `source`, fed to the standard input of rustdoc and read from the `-` input. The
+    /// name and type of the crate can't be inferred from a file then, so `--crate-name` and
+    /// `--crate-type` are set too, and `crate_name` must be valid, see [`Rustdoc::crate_name`].
+    #[track_caller]
+    pub fn stdin_source(
+        &mut self,
+        source: &str,
+        crate_name: &str,
+        crate_type: CrateType,
+    ) -> &mut Self {
+        self.stdin(source).input("-").crate_name(crate_name).crate_types(&[crate_type])
+    }
+
+    /// Feed the file at `path` to the standard input of rustdoc. It is only opened when rustdoc
+    /// is run, and is not read into memory.
+    pub fn stdin_path<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.stdin = Some(Stdin::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Specify output directory. Panics if [`Rustdoc::legacy_output`] was used, rustdoc rejects
+    /// both together.
+    #[track_caller]
     pub fn out_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        assert!(
+            self.option_value(&["--output"]).is_none(),
+            "`--out-dir` and `--output` are mutually exclusive"
+        );
         self.cmd.arg("--out-dir").arg(path.as_ref());
         self
     }
 
-    /// Given a `path`, pass `@{path}` to `rustdoc` as an
-    /// [arg file](https://doc.rust-lang.org/rustdoc/command-line-arguments.html#path-load-command-line-flags-from-a-path).
-    pub fn arg_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
-        self.cmd.arg(format!("@{}", path.as_ref().display()));
+    /// Specify output directory with the deprecated `--output`, which tests of the deprecation
+    /// still need. Prefer [`Rustdoc::out_dir`] otherwise. Panics if [`Rustdoc::out_dir`] was used,
+    /// rustdoc rejects both together.
+    #[track_caller]
+    pub fn legacy_output<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        assert!(
+            self.option_value(&["--out-dir", "-o"]).is_none(),
+            "`--output` and `--out-dir` are mutually exclusive"
+        );
+        self.cmd.arg("--output").arg(path.as_ref());
         self
     }
 
+    /// Specify the crate name. Panics if it is not a valid crate name, see
+    /// [`Rustdoc::try_crate_name`] for a non-panicking version.
     #[track_caller]
-    pub fn run_fail_assert_exit_code(&mut self, code: i32) -> Output {
-        let caller_location = std::panic::Location::caller();
-        let caller_line_number = caller_location.line();
+    pub fn crate_name<S: AsRef<OsStr>>(&mut self, name: S) -> &mut Self {
+        match self.try_crate_name(name) {
+            Ok(this) => this,
+            Err(err) => panic!("{err}"),
+        }
+    }
 
-        let output = self.cmd.output().unwrap();
-        if output.status.code().unwrap() != code {
-            handle_failed_output(&self.cmd, output, caller_line_number);
+    /// Specify the crate name, or return an error if it is not made of letters, digits and
+    /// underscores, or starts with a digit.
+    pub fn try_crate_name<S: AsRef<OsStr>>(
+        &mut self,
+        name: S,
+    ) -> Result<&mut Self, CrateNameError> {
+        check_crate_name(&name.as_ref().to_string_lossy())?;
+        self.cmd.arg("--crate-name").arg(name);
+        Ok(self)
+    }
+
+    /// Specify where an external library is located. Panics if the crate name contains
+    /// whitespace or path separators, see [`Rustdoc::try_extern_`] for a non-panicking version.
+    #[track_caller]
+    pub fn extern_<P: AsRef<Path>>(&mut self, crate_name: &str, path: P) -> &mut Self {
+        match self.try_extern_(crate_name, path) {
+            Ok(this) => this,
+            Err(err) => panic!("{err}"),
         }
-        output
     }
+
+    /// Specify where an external library is located, or return an error if the crate name
+    /// contains whitespace or path separators.
+    pub fn try_extern_<P: AsRef<Path>>(
+        &mut self,
+        crate_name: &str,
+        path: P,
+    ) -> Result<&mut Self, ExternError> {
+        check_extern_crate_name(crate_name)?;
+
+        let path = path.as_ref().to_string_lossy();
+
+        self.cmd.arg("--extern");
+        self.cmd.arg(format!("{crate_name}={path}"));
+
+        Ok(self)
+    }
+
+    /// Canonicalize the paths of all the `--extern` passed when rustdoc is spawned. Relative paths
+    /// are then resolved from the directory the test runs in, even with
+    /// [`current_dir`](Rustdoc::current_dir). Spawning panics if one of the paths doesn't exist.
+    pub fn canonicalize_externs(&mut self) -> &mut Self {
+        self.canonical_externs = true;
+        self
+    }
+
+    /// Specify where several external libraries are located, adding an `--extern` per entry in
+    /// the order of the map. Panics if a crate name is invalid, like [`Rustdoc::extern_`].
+    pub fn extern_from_map<P: AsRef<Path>>(&mut self, deps: &BTreeMap<String, P>) -> &mut Self {
+        for (crate_name, path) in deps {
+            self.extern_(crate_name, path);
+        }
+        self
+    }
+
+    /// Add a directory to the library search path (`-L`).
+    pub fn library_search_path<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.cmd.arg("-L").arg(path.as_ref());
+        self
+    }
+
+    /// Add a directory to the library search path for a specific kind of library
+    /// (`-L kind=path`).
+    pub fn library_search_path_kind<P: AsRef<Path>>(
+        &mut self,
+        kind: LibraryKind,
+        path: P,
+    ) -> &mut Self {
+        let mut arg = OsString::from(kind.as_str());
+        arg.push("=");
+        arg.push(path.as_ref());
+        self.cmd.arg("-L").arg(&arg);
+        self
+    }
+
+    /// Override the system root (`--sysroot`).
+    pub fn sysroot<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.cmd.arg("--sysroot").arg(path.as_ref());
+        self
+    }
+
+    /// Specify the edition year.
+    pub fn edition(&mut self, edition: &str) -> &mut Self {
+        self.cmd.arg("--edition").arg(edition);
+        self
+    }
+
+    /// Specify the crate version shown in the docs (`--crate-version`).
+    pub fn crate_version(&mut self, version: &str) -> &mut Self {
+        self.cmd.arg("--crate-version").arg(version);
+        self
+    }
+
+    /// Pass a codegen option (`-C {opt}`), which also applies when compiling doctests. Can be
+    /// called multiple times.
+    pub fn codegen(&mut self, opt: &str) -> &mut Self {
+        self.cmd.arg("-C").arg(opt);
+        self
+    }
+
+    /// Pass a codegen option with a value (`-C {key}={value}`).
+    pub fn codegen_kv(&mut self, key: &str, value: &str) -> &mut Self {
+        self.codegen(&format!("{key}={value}"))
+    }
+
+    /// Compile the doctests for the CPU `cpu` (`-C target-cpu={cpu}`), like `native`.
+    pub fn target_cpu(&mut self, cpu: &str) -> &mut Self {
+        self.codegen_kv("target-cpu", cpu)
+    }
+
+    /// Enable or disable a target feature when compiling the doctests, like `+avx2`
+    /// (`-C target-feature={feature}`). Can be called multiple times, the features are then
+    /// joined with commas in a single `-C target-feature`, passed after the other arguments when
+    /// rustdoc is spawned.
+    pub fn target_feature(&mut self, feature: &str) -> &mut Self {
+        self.target_features.push(feature.to_string());
+        self
+    }
+
+    /// Given a `path`, pass `@{path}` to `rustdoc` as an
+    /// [arg file](https://doc.rust-lang.org/rustdoc/command-line-arguments.html#path-load-command-line-flags-from-a-path).
+    pub fn arg_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.cmd.arg(format!("@{}", path.as_ref().display()));
+        self
+    }
+
+    /// Pass all the arguments through an arg file written when rustdoc is spawned, like with
+    /// [`Rustdoc::arg_file`], rather than on the command line. Only the program stays there, for
+    /// invocations with enough arguments to hit the length limit of command lines on Windows.
+    pub fn use_arg_file(&mut self) -> &mut Self {
+        self.via_arg_file = true;
+        self
+    }
+
+    /// Specify the output format. This validates `format` against the formats known to rustdoc,
+    /// prefer [`Rustdoc::output_format_typed`] in new code.
+    #[track_caller]
+    pub fn output_format(&mut self, format: &str) -> &mut Self {
+        let format = match format {
+            "html" => OutputFormat::Html,
+            "json" => OutputFormat::Json,
+            _ => panic!("unknown output format `{format}`, expected one of `html` or `json`"),
+        };
+        self.output_format_typed(format)
+    }
+
+    /// Specify the output format. `json` is unstable, so `-Z unstable-options` is added for it.
+    pub fn output_format_typed(&mut self, format: OutputFormat) -> &mut Self {
+        if format == OutputFormat::Json {
+            self.unstable_options();
+        }
+        self.cmd.arg("--output-format").arg(format.as_str());
+        self
+    }
+
+    /// Specify the crate type(s), as a comma-separated list like `lib,proc-macro`. Prefer
+    /// [`Rustdoc::crate_types`] in new code.
+    #[track_caller]
+    pub fn crate_type(&mut self, crate_type: &str) -> &mut Self {
+        let kinds: Vec<_> = crate_type
+            .split(',')
+            .map(|kind| {
+                CrateType::from_str(kind)
+                    .unwrap_or_else(|| panic!("unknown crate type `{kind}` in `{crate_type}`"))
+            })
+            .collect();
+        self.crate_types(&kinds)
+    }
+
+    /// Specify the crate types, passed as a single comma-joined `--crate-type`. Repeated kinds
+    /// are only passed once.
+    pub fn crate_types(&mut self, kinds: &[CrateType]) -> &mut Self {
+        self.cmd.arg("--crate-type").arg(join_deduplicated(kinds.iter().map(|kind| kind.as_str())));
+        self
+    }
+
+    /// Configure the compilation environment with `--cfg`. A cfg which was already passed is
+    /// skipped.
+    pub fn cfg(&mut self, cfg: &str) -> &mut Self {
+        if !self.has_flag("--cfg", cfg) {
+            self.cmd.arg("--cfg").arg(cfg);
+        }
+        self
+    }
+
+    /// Pass `--cfg` for each of the given cfgs, in order, skipping duplicates.
+    pub fn cfgs<I, S>(&mut self, cfgs: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for cfg in cfgs {
+            self.cfg(cfg.as_ref());
+        }
+        self
+    }
+
+    /// Set the level of lint `name`. Lint flags are passed in the order they are added, so a
+    /// later call overrides an earlier one for the same lint.
+    pub fn lint(&mut self, level: LintLevel, name: &str) -> &mut Self {
+        self.cmd.arg(level.short_flag()).arg(name);
+        self
+    }
+
+    /// Allow lint `name` (`-A`).
+    pub fn allow(&mut self, name: &str) -> &mut Self {
+        self.lint(LintLevel::Allow, name)
+    }
+
+    /// Warn on lint `name` (`-W`).
+    pub fn warn(&mut self, name: &str) -> &mut Self {
+        self.lint(LintLevel::Warn, name)
+    }
+
+    /// Deny lint `name` (`-D`).
+    pub fn deny(&mut self, name: &str) -> &mut Self {
+        self.lint(LintLevel::Deny, name)
+    }
+
+    /// Forbid lint `name` (`-F`).
+    pub fn forbid(&mut self, name: &str) -> &mut Self {
+        self.lint(LintLevel::Forbid, name)
+    }
+
+    /// Warn on lint `name` whatever its level is set to elsewhere, `--cap-lints` included
+    /// (`--force-warn`). Can be called multiple times.
+    pub fn force_warn(&mut self, name: &str) -> &mut Self {
+        self.cmd.arg("--force-warn").arg(name);
+        self
+    }
+
+    /// Silence all the warnings (`-A warnings`), except for the lints passed to
+    /// [`Rustdoc::force_warn`], to focus on one of them or on errors.
+    pub fn allow_all_lints(&mut self) -> &mut Self {
+        self.allow("warnings")
+    }
+
+    /// Cap the level of all lints with `--cap-lints`.
+    pub fn cap_lints(&mut self, level: LintLevel) -> &mut Self {
+        self.cmd.arg("--cap-lints").arg(level.as_str());
+        self
+    }
+
+    /// Document private items (`--document-private-items`).
+    pub fn document_private_items(&mut self) -> &mut Self {
+        self.cmd.arg("--document-private-items");
+        self
+    }
+
+    /// Document `#[doc(hidden)]` items (`--document-hidden-items`). This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn document_hidden_items(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--document-hidden-items");
+        self
+    }
+
+    /// Document private and/or hidden items, depending on the flags given.
+    pub fn document_visibility(&mut self, private: bool, hidden: bool) -> &mut Self {
+        if private {
+            self.document_private_items();
+        }
+        if hidden {
+            self.document_hidden_items();
+        }
+        self
+    }
+
+    /// Compile doctests without running them (`--no-run`). This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn no_run(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--no-run");
+        self
+    }
+
+    /// Specify the working directory in which doctests are run.
+    pub fn test_run_directory<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.cmd.arg("--test-run-directory").arg(dir.as_ref());
+        self
+    }
+
+    /// Pass an argument to the libtest harness running the doctests (`--test-args`), like
+    /// `--test-threads=1`. Can be called multiple times, the arguments keep their order. Rustdoc
+    /// splits each of them on whitespace.
+    pub fn test_args(&mut self, arg: &str) -> &mut Self {
+        self.cmd.arg("--test-args").arg(arg);
+        self
+    }
+
+    /// Let the doctests print to the standard output and error of rustdoc instead of capturing
+    /// them (`--nocapture`). Rustdoc passes `--nocapture` on to libtest too, which
+    /// `test_args("--nocapture")` alone would do without the doctests' own output getting
+    /// through. This is unstable, so `-Z unstable-options` is added too.
+    pub fn nocapture(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--nocapture");
+        self
+    }
+
+    /// Honor the `ignore-{name}` attributes of the examples when running doctests
+    /// (`--enable-per-target-ignores`): an example is then ignored when the target triple,
+    /// from `--target` or the host one, contains one of the names. This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn enable_per_target_ignores(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--enable-per-target-ignores");
+        self
+    }
+
+    /// Keep the compiled doctest executables in `dir` (`--persist-doctests`). This is unstable,
+    /// so `-Z unstable-options` is added too.
+    pub fn persist_doctests<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.unstable_options();
+        let dir = dir.as_ref().to_string_lossy();
+        self.cmd.arg("--persist-doctests").arg(dir.as_ref());
+        self
+    }
+
+    /// Run doctests through `tool` (`--runtool`), e.g. an emulator. This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn runtool<P: AsRef<Path>>(&mut self, tool: P) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--runtool").arg(tool.as_ref());
+        self
+    }
+
+    /// Pass an extra argument to the runtool (`--runtool-arg`). Can be called multiple times.
+    /// This is unstable, so `-Z unstable-options` is added too.
+    pub fn runtool_arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--runtool-arg").arg(arg);
+        self
+    }
+
+    /// Compile the doctests with the rustc-like binary at `path` instead of rustc
+    /// (`--test-builder`). This is unstable, so `-Z unstable-options` is added too.
+    pub fn test_builder<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--test-builder").arg(path.as_ref());
+        self
+    }
+
+    /// Run the doctest compiler through the wrapper at `path`, which gets the compiler and its
+    /// arguments as its own arguments (`--test-builder-wrapper`). Can be called multiple times,
+    /// the first wrapper then runs the second one and so on. This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn test_builder_wrapper<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--test-builder-wrapper").arg(path.as_ref());
+        self
+    }
+
+    /// Include the contents of `file` in the `<head>` of every page (`--html-in-header`). Can be
+    /// called multiple times.
+    #[track_caller]
+    pub fn html_in_header<P: AsRef<Path>>(&mut self, file: P) -> &mut Self {
+        self.html_injection("--html-in-header", file.as_ref())
+    }
+
+    /// Include the contents of `file` after `<body>` on every page (`--html-before-content`).
+    /// Can be called multiple times.
+    #[track_caller]
+    pub fn html_before_content<P: AsRef<Path>>(&mut self, file: P) -> &mut Self {
+        self.html_injection("--html-before-content", file.as_ref())
+    }
+
+    /// Include the contents of `file` before `</body>` on every page (`--html-after-content`).
+    /// Can be called multiple times.
+    #[track_caller]
+    pub fn html_after_content<P: AsRef<Path>>(&mut self, file: P) -> &mut Self {
+        self.html_injection("--html-after-content", file.as_ref())
+    }
+
+    #[track_caller]
+    fn html_injection(&mut self, flag: &str, file: &Path) -> &mut Self {
+        assert!(file.exists(), "file passed to `{flag}` does not exist: `{}`", file.display());
+        self.cmd.arg(flag).arg(file);
+        self
+    }
+
+    /// Append the rules of `css` to the `theme.css` rustdoc writes (`--extend-css`). The file
+    /// must exist. `--extend-css` is stable, so this doesn't add `-Z unstable-options`. See
+    /// [`assert_css_contains`] to check the result.
+    #[track_caller]
+    pub fn extend_css<P: AsRef<Path>>(&mut self, css: P) -> &mut Self {
+        let css = css.as_ref();
+        assert!(css.exists(), "file passed to `--extend-css` does not exist: `{}`", css.display());
+        self.cmd.arg("--extend-css").arg(css);
+        self
+    }
+
+    /// Add a custom theme (`--theme`). Can be called multiple times. The file must exist and
+    /// have a `.css` extension: rustdoc would otherwise quietly fall back to the light theme.
+    #[track_caller]
+    pub fn theme<P: AsRef<Path>>(&mut self, css: P) -> &mut Self {
+        let css = css.as_ref();
+        assert!(
+            css.extension().is_some_and(|ext| ext == "css"),
+            "theme file must have a `.css` extension: `{}`",
+            css.display()
+        );
+        assert!(css.exists(), "theme file does not exist: `{}`", css.display());
+        self.cmd.arg("--theme").arg(css);
+        self
+    }
+
+    /// Set the default theme by name (`--default-theme`).
+    pub fn default_theme(&mut self, name: &str) -> &mut Self {
+        self.cmd.arg("--default-theme").arg(name);
+        self
+    }
+
+    /// Set the default value of the UI setting `name`, like `line-numbers` or `theme`, baked into
+    /// the pages (`--default-setting name=value`). Can be called multiple times, once per
+    /// setting. Panics if `name` is empty or contains `=`. This is unstable, so
+    /// `-Z unstable-options` is added too.
+    #[track_caller]
+    pub fn default_setting(&mut self, name: &str, value: &str) -> &mut Self {
+        assert!(
+            !name.is_empty() && !name.contains('='),
+            "setting name must be non-empty and cannot contain `=`: `{name}`"
+        );
+        self.unstable_options();
+        self.cmd.arg("--default-setting").arg(format!("{name}={value}"));
+        self
+    }
+
+    /// Write the calls scraped from this crate to `path` (`--scrape-examples-output-path`).
+    /// This is unstable, so `-Z unstable-options` is added too.
+    pub fn scrape_examples_output_path<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--scrape-examples-output-path").arg(path.as_ref());
+        self
+    }
+
+    /// Scrape calls to crate `name` (`--scrape-examples-target-crate`). This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn scrape_examples_target_crate(&mut self, name: &str) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--scrape-examples-target-crate").arg(name);
+        self
+    }
+
+    /// Include examples from a calls file written by a scraping run (`--with-examples`), which
+    /// must exist. Can be called multiple times, once per scraped crate. This is unstable, so
+    /// `-Z unstable-options` is added too.
+    #[track_caller]
+    pub fn with_examples<P: AsRef<Path>>(&mut self, calls: P) -> &mut Self {
+        let calls = calls.as_ref();
+        assert!(calls.exists(), "calls file does not exist: `{}`", calls.display());
+        self.unstable_options();
+        self.cmd.arg("--with-examples").arg(calls);
+        self
+    }
+
+    /// Pass `--with-examples` for each of the given calls files, in sorted order, see
+    /// [`Rustdoc::with_examples`].
+    #[track_caller]
+    pub fn with_examples_all<I, P>(&mut self, calls: I) -> &mut Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut calls: Vec<_> = calls.into_iter().map(|path| path.as_ref().to_owned()).collect();
+        calls.sort();
+        for path in calls {
+            self.with_examples(path);
+        }
+        self
+    }
+
+    /// Report documentation coverage instead of generating docs (`--show-coverage`). This is
+    /// unstable, so `-Z unstable-options` is added too.
+    pub fn show_coverage(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--show-coverage");
+        self
+    }
+
+    /// Specify the format diagnostics are emitted in.
+    pub fn error_format(&mut self, format: ErrorFormat) -> &mut Self {
+        self.cmd.arg(format!("--error-format={}", format.as_str()));
+        self
+    }
+
+    /// Configure the JSON diagnostics, passed as a single comma-joined `--json`. Repeated options
+    /// are only passed once. `--error-format=json` is added too, rustdoc requires it. Panics if
+    /// [`Rustdoc::color`] was used, rustdoc rejects both together: use
+    /// [`JsonOpt::DiagnosticRenderedAnsi`] to color the diagnostics instead.
+    #[track_caller]
+    pub fn json_config(&mut self, opts: &[JsonOpt]) -> &mut Self {
+        assert!(
+            self.option_value(&["--color"]).is_none(),
+            "`--json` and `--color` are mutually exclusive"
+        );
+        self.json_error_format();
+        self.cmd.arg(format!("--json={}", join_deduplicated(opts.iter().map(|opt| opt.as_str()))));
+        self
+    }
+
+    /// Specify whether diagnostics are colored. rustdoc defaults to `auto`. Panics if
+    /// [`Rustdoc::json_config`] was used, rustdoc rejects both together.
+    #[track_caller]
+    pub fn color(&mut self, choice: ColorChoice) -> &mut Self {
+        assert!(
+            self.option_value(&["--json"]).is_none(),
+            "`--color` and `--json` are mutually exclusive"
+        );
+        self.cmd.arg(format!("--color={}", choice.as_str()));
+        self
+    }
+
+    /// Specify the width diagnostics are rendered for, instead of the width rustdoc infers from
+    /// the terminal (`--diagnostic-width`).
+    pub fn diagnostic_width(&mut self, cols: u32) -> &mut Self {
+        self.cmd.arg("--diagnostic-width").arg(cols.to_string());
+        self
+    }
+
+    /// Add a "Run" button sending the examples to the playground at `url` (`--playground-url`).
+    /// `#![doc(html_playground_url)]` overrides it. Panics if `url` is not an absolute `http(s)`
+    /// URL. This is unstable, so `-Z unstable-options` is added too.
+    #[track_caller]
+    pub fn playground_url(&mut self, url: &str) -> &mut Self {
+        check_playground_url("--playground-url", url);
+        self.unstable_options();
+        self.cmd.arg("--playground-url").arg(url);
+        self
+    }
+
+    /// Like [`Rustdoc::playground_url`], but only for the code blocks of standalone markdown
+    /// files, see [`Rustdoc::markdown`] (`--markdown-playground-url`). For those it takes
+    /// precedence over `--playground-url`. Panics if `url` is not an absolute `http(s)` URL.
+    #[track_caller]
+    pub fn markdown_playground_url(&mut self, url: &str) -> &mut Self {
+        check_playground_url("--markdown-playground-url", url);
+        self.cmd.arg("--markdown-playground-url").arg(url);
+        self
+    }
+
+    /// Remap source paths starting with `from` to `to` in the output (`--remap-path-prefix`).
+    /// Can be called multiple times. Neither side may contain `=`, as that would make the
+    /// mapping ambiguous.
+    #[track_caller]
+    pub fn remap_path_prefix<P: AsRef<Path>, Q: AsRef<Path>>(
+        &mut self,
+        from: P,
+        to: Q,
+    ) -> &mut Self {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        for path in [from, to] {
+            assert!(
+                !path.to_string_lossy().contains('='),
+                "path passed to `--remap-path-prefix` cannot contain `=`: `{}`",
+                path.display()
+            );
+        }
+        let mut arg = OsString::new();
+        arg.push(from);
+        arg.push("=");
+        arg.push(to);
+        self.cmd.arg("--remap-path-prefix").arg(&arg);
+        self
+    }
+
+    /// Link to the docs of extern crate `crate_name` hosted at `url`
+    /// (`--extern-html-root-url`). This is unstable, so `-Z unstable-options` is added too.
+    #[track_caller]
+    pub fn extern_html_root_url(&mut self, crate_name: &str, url: &str) -> &mut Self {
+        if let Err(err) = check_extern_crate_name(crate_name) {
+            panic!("{err}");
+        }
+        self.unstable_options();
+        self.cmd.arg("--extern-html-root-url").arg(format!("{crate_name}={url}"));
+        self
+    }
+
+    /// Prefer `--extern-html-root-url` over local docs (`--extern-html-root-takes-precedence`).
+    /// This is unstable, so `-Z unstable-options` is added too.
+    pub fn extern_html_root_takes_precedence(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--extern-html-root-takes-precedence");
+        self
+    }
+
+    /// Only run rustdoc's checks, without generating docs (`--check`). This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn check(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--check");
+        self
+    }
+
+    /// Link the identifiers of the source pages to their definition
+    /// (`--generate-link-to-definition`). This is unstable, so `-Z unstable-options` is added too.
+    pub fn generate_link_to_definition(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--generate-link-to-definition");
+        self
+    }
+
+    /// Add a "Layout" section with the size of the types to their pages (`--show-type-layout`).
+    /// This is unstable, so `-Z unstable-options` is added too.
+    pub fn show_type_layout(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--show-type-layout");
+        self
+    }
+
+    /// Generate an index page listing the documented crates (`--enable-index-page`). This is
+    /// unstable, so `-Z unstable-options` is added too. Panics if [`Rustdoc::index_page`] was
+    /// used: it already implies `--enable-index-page`, so passing both is redundant.
+    #[track_caller]
+    pub fn enable_index_page(&mut self) -> &mut Self {
+        assert!(
+            self.option_value(&["--index-page"]).is_none(),
+            "`--index-page` was already passed, it implies `--enable-index-page`"
+        );
+        self.unstable_options();
+        self.cmd.arg("--enable-index-page");
+        self
+    }
+
+    /// Use the markdown file `md` as the index page (`--index-page`). The file must exist. This is
+    /// unstable, so `-Z unstable-options` is added too. Panics if
+    /// [`Rustdoc::enable_index_page`] was used: this flag implies it, so passing both is
+    /// redundant.
+    #[track_caller]
+    pub fn index_page<P: AsRef<Path>>(&mut self, md: P) -> &mut Self {
+        let md = md.as_ref();
+        assert!(
+            !self.cmd.get_args().any(|arg| arg == "--enable-index-page"),
+            "`--index-page` implies `--enable-index-page`, which was already passed"
+        );
+        assert!(md.exists(), "index page does not exist: `{}`", md.display());
+        self.unstable_options();
+        self.cmd.arg("--index-page").arg(md);
+        self
+    }
+
+    /// Add `suffix` to the name of the files rustdoc generates for this invocation, like
+    /// `search-index{suffix}.js` (`--resource-suffix`). See [`resolved_asset`] to find them. This
+    /// is unstable, so `-Z unstable-options` is added too.
+    pub fn resource_suffix(&mut self, suffix: &str) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--resource-suffix").arg(suffix);
+        self
+    }
+
+    /// Make the pages load the shared static files, like the CSS and the fonts, from `path`
+    /// instead of the output directory (`--static-root-path`). This is unstable, so
+    /// `-Z unstable-options` is added too.
+    pub fn static_root_path(&mut self, path: &str) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--static-root-path").arg(path);
+        self
+    }
+
+    /// Write a `redirect-map.json` mapping the redirect pages to their target, instead of
+    /// generating the redirect pages (`--generate-redirect-map`). See [`read_redirect_map`] to
+    /// read it. This is unstable, so `-Z unstable-options` is added too.
+    ///
+    /// [`read_redirect_map`]: crate::read_redirect_map
+    pub fn generate_redirect_map(&mut self) -> &mut Self {
+        self.unstable_options();
+        self.cmd.arg("--generate-redirect-map");
+        self
+    }
+
+    /// Specify the kinds of output to emit, passed as a single comma-joined `--emit`. Repeated
+    /// kinds are only passed once. This is unstable, so `-Z unstable-options` is added too.
+    pub fn emit(&mut self, kinds: &[Emit]) -> &mut Self {
+        self.unstable_options();
+        self.cmd
+            .arg(format!("--emit={}", join_deduplicated(kinds.iter().map(|kind| kind.as_str()))));
+        self
+    }
+
+    /// Pass the legacy `--passes {pass}` flag. Can be called multiple times. rustdoc no longer
+    /// runs custom passes and only warns about it, this is kept for regression tests covering
+    /// that.
+    pub fn passes(&mut self, pass: &str) -> &mut Self {
+        self.cmd.arg("--passes").arg(pass);
+        self
+    }
+
+    /// Pass the legacy `--no-defaults` flag, see [`Rustdoc::passes`].
+    pub fn no_defaults(&mut self) -> &mut Self {
+        self.cmd.arg("--no-defaults");
+        self
+    }
+
+    /// Pass the unstable `-Z {flag}`, unless the exact same flag is already present. All the
+    /// methods which need `-Z unstable-options` go through this, so it is only passed once.
+    pub fn unstable_flag(&mut self, flag: &str) -> &mut Self {
+        if !self.has_flag("-Z", flag) {
+            self.cmd.arg("-Z").arg(flag);
+        }
+        self
+    }
+
+    /// Pass `--error-format=json`, unless it is already present.
+    fn json_error_format(&mut self) -> &mut Self {
+        if !self.has_flag("--error-format", "json") {
+            self.error_format(ErrorFormat::Json);
+        }
+        self
+    }
+
+    /// Pass `-Z unstable-options`, unless it is already present.
+    fn unstable_options(&mut self) -> &mut Self {
+        self.unstable_flag("unstable-options")
+    }
+
+    /// The value of the last of the given options which was passed, either as a separate
+    /// argument or joined as `{option}={value}`.
+    fn option_value(&self, options: &[&str]) -> Option<OsString> {
+        let args: Vec<_> = self.cmd.get_args().collect();
+        let mut value = None;
+        for (i, arg) in args.iter().enumerate() {
+            let Some(arg) = arg.to_str() else { continue };
+            for option in options {
+                if arg == *option {
+                    value = args.get(i + 1).map(|value| value.to_os_string());
+                } else if let Some(joined) =
+                    arg.strip_prefix(option).and_then(|rest| rest.strip_prefix('='))
+                {
+                    value = Some(joined.into());
+                }
+            }
+        }
+        value
+    }
+
+    /// A new command with the program, environment, working directory and arguments of this
+    /// invocation.
+    fn copied(&self) -> Command {
+        let mut cmd = self.command_like();
+        cmd.args(self.cmd.get_args());
+        cmd
+    }
+
+    /// A new command with the program, environment and working directory of this invocation, but
+    /// none of its arguments.
+    fn command_like(&self) -> Command {
+        let mut like = Command::new(self.cmd.get_program());
+        if self.env_cleared {
+            like.env_clear();
+        }
+        for (key, value) in self.cmd.get_envs() {
+            match value {
+                Some(value) => like.env(key, value),
+                None => like.env_remove(key),
+            };
+        }
+        if let Some(dir) = self.cmd.get_current_dir() {
+            like.current_dir(dir);
+        }
+        like
+    }
+
+    /// A clone of this invocation without the output directories it was given.
+    fn without_out_dir(&self) -> Self {
+        const OPTIONS: [&str; 3] = ["--out-dir", "-o", "--output"];
+        let mut clone = self.clone();
+        let mut cmd = self.command_like();
+        let mut args = self.cmd.get_args();
+        while let Some(arg) = args.next() {
+            let option = arg.to_str().unwrap_or_default();
+            if OPTIONS.contains(&option) {
+                args.next();
+            } else if !OPTIONS.iter().any(|opt| option.starts_with(&format!("{opt}="))) {
+                cmd.arg(arg);
+            }
+        }
+        clone.cmd = cmd;
+        clone
+    }
+
+    /// The directory docs are written to: the last `--out-dir`/`-o`/`--output` passed, or
+    /// rustdoc's default of `doc`.
+    fn configured_out_dir(&self) -> PathBuf {
+        self.option_value(&["--out-dir", "-o", "--output"]).unwrap_or_else(|| "doc".into()).into()
+    }
+
+    /// The crate name: the last `--crate-name` passed, or the name rustdoc infers from the last
+    /// `.rs` input.
+    fn configured_crate_name(&self) -> Option<String> {
+        if let Some(name) = self.option_value(&["--crate-name"]) {
+            return Some(name.to_string_lossy().into_owned());
+        }
+        let input = self
+            .cmd
+            .get_args()
+            .filter(|arg| Path::new(arg).extension() == Some("rs".as_ref()))
+            .last()?;
+        Some(Path::new(input).file_stem()?.to_string_lossy().replace('-', "_"))
+    }
+
+    /// Check whether `flag` was already passed with `value`, either as two separate arguments or
+    /// joined as `{flag}{value}` or `{flag}={value}`.
+    fn has_flag(&self, flag: &str, value: &str) -> bool {
+        let args: Vec<_> = self.cmd.get_args().collect();
+        let joined = format!("{flag}{value}");
+        let joined_eq = format!("{flag}={value}");
+        args.iter().any(|arg| *arg == joined.as_str() || *arg == joined_eq.as_str())
+            || args.windows(2).any(|pair| pair[0] == flag && pair[1] == value)
+    }
+
+    /// Run the doctests (adding `--test` if it wasn't passed already) and return the counts
+    /// from the libtest summary. The invocation is expected to succeed unless some doctests
+    /// failed.
+    #[track_caller]
+    pub fn run_doctests(&mut self) -> DoctestSummary {
+        let caller_location = std::panic::Location::caller();
+        let caller_line_number = caller_location.line();
+
+        if !self.cmd.get_args().any(|arg| arg == "--test") {
+            self.cmd.arg("--test");
+        }
+        let output = self.command_output();
+        let summary = std::str::from_utf8(&output.stdout).ok().and_then(DoctestSummary::parse);
+        match summary {
+            Some(summary) if output.status.success() == (summary.failed == 0) => summary,
+            _ => handle_failed_output(&self.cmd, output, caller_line_number),
+        }
+    }
+
+    /// Run with `--show-coverage --output-format json`, assert that it succeeds and return the
+    /// parsed coverage report.
+    #[track_caller]
+    pub fn run_coverage(&mut self) -> CoverageReport {
+        let caller_location = std::panic::Location::caller();
+        let caller_line_number = caller_location.line();
+
+        self.show_coverage().output_format_typed(OutputFormat::Json);
+        let output = self.command_output();
+        if !output.status.success() {
+            handle_failed_output(&self.cmd, output, caller_line_number);
+        }
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        CoverageReport::parse(&stdout)
+            .unwrap_or_else(|| panic!("failed to parse coverage report:\n{stdout}"))
+    }
+
+    /// Run with `--error-format=json` and return the emitted diagnostics. The exit status is not
+    /// checked, as emitting errors makes rustdoc fail.
+    pub fn run_and_collect_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.json_error_format();
+        let output = self.command_output();
+        parse_diagnostics(&String::from_utf8(output.stderr).unwrap())
+    }
+
+    /// Run in `--check` mode with `--error-format=json` and return the emitted diagnostics.
+    pub fn run_check(&mut self) -> Vec<Diagnostic> {
+        self.check().run_and_collect_diagnostics()
+    }
+
+    /// Run and assert that the standard error matches the `golden` file, printing a diff of the
+    /// two otherwise. Before comparing, the sysroot is replaced by `$SYSROOT`, `TMPDIR` by
+    /// `$TMPDIR` and the working directory of rustdoc by `$DIR`. When `RUSTC_BLESS_TEST` is set,
+    /// `golden` is overwritten with the normalized standard error instead. The exit status is not
+    /// checked, as emitting errors makes rustdoc fail.
+    #[track_caller]
+    pub fn run_and_diff_stderr<P: AsRef<Path>>(&mut self, golden: P) -> CompletedProcess {
+        let golden = golden.as_ref();
+        let process = CompletedProcess::from(self.command_output());
+        // The working directory of rustdoc is the one of the test unless it was set, relative to
+        // the latter if it isn't absolute.
+        let mut work_dir = env::current_dir().unwrap();
+        if let Some(dir) = self.cmd.get_current_dir() {
+            work_dir.push(dir);
+        }
+        let tmp_dir = tmp_dir();
+        let (tmp_dir, work_dir) = (tmp_dir.to_string_lossy(), work_dir.to_string_lossy());
+        let mut replacements = [(&*tmp_dir, "$TMPDIR"), (&*work_dir, "$DIR")];
+        // Either may be within the other, which is then replaced first.
+        replacements.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+        let actual = process.normalized_stderr(&replacements);
+        if env::var_os("RUSTC_BLESS_TEST").is_some() {
+            fs::write(golden, &actual)
+                .unwrap_or_else(|err| panic!("failed to bless `{}`: {err}", golden.display()));
+            return process;
+        }
+        let expected = fs::read_to_string(golden)
+            .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", golden.display()));
+        if expected != actual {
+            panic!(
+                "the stderr of rustdoc does not match `{}`, \
+                set `RUSTC_BLESS_TEST` to update it:\n{}",
+                golden.display(),
+                unified_diff(&expected, &actual)
+            );
+        }
+        process
+    }
+
+    /// Run with `--output-format json`, assert that it succeeds and return the parsed JSON
+    /// documentation of the crate. The output is looked up in the configured out dir by crate
+    /// name, so either `--crate-name` or a `.rs` input must have been passed.
+    #[track_caller]
+    pub fn run_json(&mut self) -> RustdocJson {
+        RustdocJson::read(self.run_json_output())
+    }
+
+    /// Run with `--output-format json`, assert that it succeeds and that the emitted JSON has
+    /// format version `expected`. See [`Rustdoc::run_json`] for how the output is found.
+    #[track_caller]
+    pub fn assert_json_format_version(&mut self, expected: u32) {
+        assert_format_version(self.run_json_output(), expected);
+    }
+
+    /// Run with `--output-format json`, assert that it succeeds and return the path of the
+    /// emitted JSON.
+    #[track_caller]
+    fn run_json_output(&mut self) -> PathBuf {
+        let caller_location = std::panic::Location::caller();
+        let caller_line_number = caller_location.line();
+
+        self.output_format_typed(OutputFormat::Json);
+        let output = self.command_output();
+        if !output.status.success() {
+            handle_failed_output(&self.cmd, output, caller_line_number);
+        }
+        let crate_name = self
+            .configured_crate_name()
+            .expect("cannot find the JSON output without `--crate-name` or a `.rs` input");
+        self.configured_out_dir().join(format!("{crate_name}.json"))
+    }
+
+    #[track_caller]
+    pub fn run_fail_assert_exit_code(&mut self, code: i32) -> Output {
+        let caller_location = std::panic::Location::caller();
+        let caller_line_number = caller_location.line();
+
+        let output = self.command_output();
+        if output.status.code().unwrap() != code {
+            handle_failed_output(&self.cmd, output, caller_line_number);
+        }
+        output
+    }
+
+    /// Document `input` and return the directory of the docs of its crate, e.g. `{out_dir}/foo`.
+    /// Unless an output directory was configured, a new one is picked in `TMPDIR`.
+    #[track_caller]
+    pub fn document<P: AsRef<Path>>(&mut self, input: P) -> PathBuf {
+        static OUT_DIRS: AtomicUsize = AtomicUsize::new(0);
+        self.input(input);
+        if self.option_value(&["--out-dir", "-o", "--output"]).is_none() {
+            let id = OUT_DIRS.fetch_add(1, Ordering::Relaxed);
+            self.out_dir(tmp_dir().join(format!("rustdoc-doc-{id}")));
+        }
+        let crate_name =
+            self.configured_crate_name().expect("the name of the documented crate is unknown");
+        self.run();
+        self.configured_out_dir().join(crate_name)
+    }
+
+    /// Run rustdoc with a new output directory in `TMPDIR`, assert that it succeeds and return its
+    /// output along with the directory, which is removed once dropped. If an output directory was
+    /// configured already, it is used instead and kept once dropped.
+    #[track_caller]
+    pub fn run_in_tempdir(&mut self) -> (CompletedProcess, TempDir) {
+        static OUT_DIRS: AtomicUsize = AtomicUsize::new(0);
+        let caller_line_number = std::panic::Location::caller().line();
+        let dir = if self.option_value(&["--out-dir", "-o", "--output"]).is_some() {
+            TempDir { dir: self.configured_out_dir(), remove: false }
+        } else {
+            let id = OUT_DIRS.fetch_add(1, Ordering::Relaxed);
+            let dir =
+                TempDir { dir: tmp_dir().join(format!("rustdoc-tempdir-{id}")), remove: true };
+            self.out_dir(dir.path());
+            dir
+        };
+        let output = self.command_output();
+        if !output.status.success() {
+            handle_failed_output(&self.cmd, output, caller_line_number);
+        }
+        (output.into(), dir)
+    }
+
+    /// Run rustdoc for at most `timeout`, killing it if it takes longer than that. Whether it was
+    /// killed is told by [`CompletedProcess::timed_out`]. Only available on unix, where the
+    /// doctests can be killed along with rustdoc: elsewhere they would outlive it and keep its
+    /// output pipes open.
+    #[cfg(unix)]
+    #[track_caller]
+    pub fn timeout(&mut self, timeout: Duration) -> CompletedProcess {
+        // Doctests run in processes of their own, put them in a group with rustdoc so that they
+        // can be killed along with it.
+        let RustdocChild { mut child, capture, stdin_writer } = self.spawn_with_stdin(None, true);
+        // The pipes are drained while waiting, so that rustdoc never blocks on a full one.
+        let stdout = child.stdout.take().map(read_in_background);
+        let stderr = child.stderr.take().map(read_in_background);
+        let deadline = Instant::now() + timeout;
+        let (status, timed_out) = loop {
+            if let Some(status) = child.try_wait().unwrap() {
+                break (status, false);
+            }
+            if Instant::now() >= deadline {
+                // SAFETY: `kill` has no memory safety requirements. The group is the one rustdoc
+                // leads, since it isn't reaped yet. Some systems don't signal a group of zombies
+                // and fail with `ESRCH`, if rustdoc and its doctests exited just now.
+                if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } != 0 {
+                    let err = io::Error::last_os_error();
+                    if err.raw_os_error() != Some(libc::ESRCH) {
+                        panic!("failed to kill the process group of rustdoc: {err}");
+                    }
+                }
+                break (child.wait().unwrap(), true);
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        let collect = |pipe: Option<thread::JoinHandle<_>>| {
+            pipe.map_or_else(Vec::new, |pipe| pipe.join().unwrap())
+        };
+        let mut output = Output { status, stdout: collect(stdout), stderr: collect(stderr) };
+        if let Some(writer) = stdin_writer {
+            writer.join().unwrap();
+        }
+        read_combined(&mut output, capture);
+        CompletedProcess::new(output, timed_out)
+    }
+
+    /// Start rustdoc without waiting for it, to control it while it runs. Unless a standard input
+    /// was configured with [`Rustdoc::stdin`] or [`Rustdoc::stdin_path`], it is piped, to be
+    /// written to through [`RustdocChild::stdin`].
+    #[track_caller]
+    pub fn spawn(&mut self) -> RustdocChild {
+        self.spawn_with_stdin(self.stdin.is_none().then(Stdio::piped), false)
+    }
+
+    /// Spawn rustdoc with its output piped and its standard input taken from `stdin` if given, or
+    /// fed from what was configured otherwise. With [`Rustdoc::capture_combined`], both outputs go
+    /// to a file instead, read back by [`RustdocChild::wait`]. With `process_group`, rustdoc runs
+    /// in a process group of its own on unix, for this spawn only.
+    #[track_caller]
+    fn spawn_with_stdin(&mut self, stdin: Option<Stdio>, process_group: bool) -> RustdocChild {
+        // A process group can't be unset, so it is only set on a copy of `self.cmd`.
+        let mut rebuilt = self.rebuilt_command().or_else(|| process_group.then(|| self.copied()));
+        let cmd = rebuilt.as_mut().unwrap_or(&mut self.cmd);
+        #[cfg(unix)]
+        if process_group {
+            std::os::unix::process::CommandExt::process_group(cmd, 0);
+        }
+        match (stdin, &self.stdin) {
+            (Some(stdin), _) => cmd.stdin(stdin),
+            (None, None) => cmd.stdin(Stdio::null()),
+            (None, Some(Stdin::File(path))) => {
+                let file = File::open(path)
+                    .unwrap_or_else(|err| panic!("failed to open `{}`: {err}", path.display()));
+                cmd.stdin(file)
+            }
+            (None, Some(Stdin::Bytes(_))) => cmd.stdin(Stdio::piped()),
+        };
+        let capture = if self.combined {
+            // Both handles share the file offset, so the writes land in the order they are made.
+            static CAPTURES: AtomicUsize = AtomicUsize::new(0);
+            let path = tmp_dir()
+                .join(format!("rustdoc-combined-{}.out", CAPTURES.fetch_add(1, Ordering::Relaxed)));
+            let file = File::create(&path)
+                .unwrap_or_else(|err| panic!("failed to create `{}`: {err}", path.display()));
+            cmd.stdout(file.try_clone().unwrap()).stderr(file);
+            Some(path)
+        } else {
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+            None
+        };
+        let mut child = cmd.spawn().unwrap();
+        // When the standard input is fed from bytes, they are written on another thread, so that
+        // rustdoc can fill its output pipes meanwhile, and the pipe is closed once they are. The
+        // pipe is left to the caller otherwise.
+        let mut stdin_writer = None;
+        if let Some(Stdin::Bytes(input)) = &self.stdin {
+            if let Some(pipe) = child.stdin.take() {
+                stdin_writer = Some(write_in_background(pipe, input.clone()));
+            }
+        }
+        RustdocChild { child, capture, stdin_writer }
+    }
+
+    /// The command to spawn instead of `self.cmd` when its arguments are rewritten at spawn
+    /// time, by [`Rustdoc::canonicalize_externs`], [`Rustdoc::target_feature`] or
+    /// [`Rustdoc::use_arg_file`].
+    #[track_caller]
+    fn rebuilt_command(&self) -> Option<Command> {
+        if !self.canonical_externs && self.target_features.is_empty() && !self.via_arg_file {
+            return None;
+        }
+        let mut args: Vec<_> = self.cmd.get_args().map(OsStr::to_os_string).collect();
+        if self.canonical_externs {
+            canonicalize_extern_paths(&mut args);
+        }
+        if !self.target_features.is_empty() {
+            args.push("-C".into());
+            args.push(format!("target-feature={}", self.target_features.join(",")).into());
+        }
+        let mut cmd = self.command_like();
+        if self.via_arg_file {
+            cmd.arg(format!("@{}", write_arg_file(&args).display()));
+        } else {
+            cmd.args(args);
+        }
+        Some(cmd)
+    }
+
+    #[track_caller]
+    fn command_output(&mut self) -> Output {
+        self.spawn_with_stdin(None, false).wait_with_output()
+    }
+}
+
+/// Move what rustdoc wrote to the combined `capture` file, if any, to the stdout of `output`.
+fn read_combined(output: &mut Output, capture: Option<PathBuf>) {
+    if let Some(path) = capture {
+        output.stdout = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+/// Write `args` to a new arg file in `TMPDIR`, one per line, and return its path. The file is
+/// kept to be looked at if rustdoc fails.
+#[track_caller]
+fn write_arg_file(args: &[OsString]) -> PathBuf {
+    static ARG_FILES: AtomicUsize = AtomicUsize::new(0);
+    let path =
+        tmp_dir().join(format!("rustdoc-args-{}.txt", ARG_FILES.fetch_add(1, Ordering::Relaxed)));
+    let mut contents = String::new();
+    for arg in args {
+        let arg = arg.to_str().unwrap_or_else(|| panic!("arg files must be UTF-8: {arg:?}"));
+        assert!(!arg.contains('\n'), "arg files can't contain arguments with newlines: {arg:?}");
+        contents.push_str(arg);
+        contents.push('\n');
+    }
+    fs::write(&path, contents)
+        .unwrap_or_else(|err| panic!("failed to write `{}`: {err}", path.display()));
+    path
+}
+
+/// Replace the path of each `--extern {name}={path}` in `args` by its canonical form. Panics if a
+/// path doesn't exist.
+#[track_caller]
+fn canonicalize_extern_paths(args: &mut [OsString]) {
+    for i in 1..args.len() {
+        if args[i - 1] != "--extern" {
+            continue;
+        }
+        let Some((name, path)) = args[i].to_str().and_then(|arg| arg.split_once('=')) else {
+            continue;
+        };
+        let canonical = fs::canonicalize(path).unwrap_or_else(|err| {
+            panic!("the path of the extern crate `{name}` can't be resolved: `{path}`: {err}")
+        });
+        let mut arg = OsString::from(format!("{name}="));
+        arg.push(canonical);
+        args[i] = arg;
+    }
+}
+
+/// Write all of `input` to `pipe` on another thread, then close it. rustdoc may exit or stop
+/// reading before the end, like when it fails early, so a broken pipe isn't an error.
+fn write_in_background(mut pipe: ChildStdin, input: Box<[u8]>) -> thread::JoinHandle<()> {
+    thread::spawn(move || match pipe.write_all(&input) {
+        Err(err) if err.kind() != ErrorKind::BrokenPipe => {
+            panic!("failed to write the standard input of rustdoc: {err}")
+        }
+        _ => {}
+    })
+}
+
+/// Read all of `pipe` on another thread.
+fn read_in_background<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut content = Vec::new();
+        pipe.read_to_end(&mut content).unwrap();
+        content
+    })
 }
@@ -0,0 +1,162 @@
+//! Reading the output of `rustdoc --output-format json`.
+
+use std::fs;
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+use rustdoc_json_types::{Crate, Id, Item, ItemEnum, FORMAT_VERSION};
+
+/// Assert that the rustdoc JSON written to `json_file` has format version `expected`. Unlike
+/// [`RustdocJson::read`], this only looks at `format_version`, so it works on any version.
+#[track_caller]
+pub fn assert_format_version<P: AsRef<Path>>(json_file: P, expected: u32) {
+    let path = json_file.as_ref();
+    let json = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", path.display()));
+    let value: serde_json::Value =
+        serde_json::from_str(&json).unwrap_or_else(|err| panic!("invalid rustdoc JSON: {err}"));
+    let actual = value.get("format_version").and_then(serde_json::Value::as_u64);
+    assert_eq!(
+        actual,
+        Some(expected.into()),
+        "`{}` has format version {actual:?}, but {expected} was expected",
+        path.display()
+    );
+}
+
+/// The parsed JSON documentation of a crate.
+#[derive(Debug, Clone)]
+pub struct RustdocJson {
+    krate: Crate,
+}
+
+impl RustdocJson {
+    /// Parse the JSON documentation of a crate. Panics if its `format_version` is not the
+    /// [`FORMAT_VERSION`] this crate was built against, rather than failing to deserialize it in
+    /// some confusing way.
+    #[track_caller]
+    pub fn parse(json: &str) -> Self {
+        let value: serde_json::Value =
+            serde_json::from_str(json).unwrap_or_else(|err| panic!("invalid rustdoc JSON: {err}"));
+        let format_version = value.get("format_version").and_then(serde_json::Value::as_u64);
+        assert_eq!(
+            format_version,
+            Some(FORMAT_VERSION.into()),
+            "rustdoc JSON has format version {format_version:?}, but {FORMAT_VERSION} was expected"
+        );
+        let krate = serde_json::from_value(value)
+            .unwrap_or_else(|err| panic!("failed to deserialize rustdoc JSON: {err}"));
+        RustdocJson { krate }
+    }
+
+    /// Read and parse the JSON documentation written to `path`, see [`RustdocJson::parse`].
+    #[track_caller]
+    pub fn read<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", path.display()));
+        Self::parse(&json)
+    }
+
+    pub fn format_version(&self) -> u32 {
+        self.krate.format_version
+    }
+
+    /// The id of the crate's root module.
+    pub fn root(&self) -> &Id {
+        &self.krate.root
+    }
+
+    /// The crate's root module.
+    pub fn root_item(&self) -> &Item {
+        &self.krate.index[&self.krate.root]
+    }
+
+    /// All the items of the crate, along with the external items they reference.
+    pub fn index(&self) -> &FxHashMap<Id, Item> {
+        &self.krate.index
+    }
+
+    /// Find the item at `path`, like `["foo", "Bar", "baz"]` for the method `baz` of struct
+    /// `foo::Bar`. The path is resolved from the crate root following the items of modules
+    /// (including re-exports), traits, impls of types and enum variants; failing that, it is
+    /// looked up in the `paths` table, which records where items were defined.
+    pub fn item_by_path(&self, path: &[&str]) -> Option<&Item> {
+        let (first, rest) = path.split_first()?;
+        let root = self.root_item();
+        let resolved = if root.name.as_deref() == Some(*first) {
+            rest.iter().try_fold(root, |item, name| self.child(item, name))
+        } else {
+            None
+        };
+        resolved.or_else(|| {
+            self.krate
+                .paths
+                .iter()
+                .filter(|(_, summary)| summary.path.iter().eq(path))
+                .find_map(|(id, _)| self.krate.index.get(id))
+        })
+    }
+
+    /// Assert that the item at `path`, resolved like [`RustdocJson::item_by_path`], is of kind
+    /// `expected_kind`: the name of its [`ItemEnum`] variant in the JSON, like `struct`,
+    /// `function` or `type_alias`. A re-export has the kind of the item it re-exports, while a
+    /// type alias is a `type_alias` whatever the type it aliases.
+    #[track_caller]
+    pub fn assert_item_kind(&self, path: &[&str], expected_kind: &str) {
+        let path_str = path.join("::");
+        let item =
+            self.item_by_path(path).unwrap_or_else(|| panic!("no item found at `{path_str}`"));
+        let kind = match serde_json::to_value(&item.inner).unwrap() {
+            serde_json::Value::String(kind) => kind,
+            serde_json::Value::Object(inner) => inner.keys().next().unwrap().clone(),
+            inner => unreachable!("unexpected serialization of an item: {inner}"),
+        };
+        assert_eq!(
+            kind, expected_kind,
+            "`{path_str}` is a `{kind}`, but a `{expected_kind}` was expected"
+        );
+    }
+
+    /// Find the child of `parent` named `name`, following re-exports.
+    fn child(&self, parent: &Item, name: &str) -> Option<&Item> {
+        let (children, impls): (&[Id], &[Id]) = match &parent.inner {
+            ItemEnum::Module(module) => (&module.items, &[]),
+            ItemEnum::Trait(trait_) => (&trait_.items, &[]),
+            ItemEnum::Struct(struct_) => (&[], &struct_.impls),
+            ItemEnum::Union(union_) => (&union_.fields, &union_.impls),
+            ItemEnum::Enum(enum_) => (&enum_.variants, &enum_.impls),
+            _ => return None,
+        };
+        let impl_items = impls.iter().filter_map(|id| match &self.krate.index.get(id)?.inner {
+            ItemEnum::Impl(impl_) => Some(&impl_.items),
+            _ => None,
+        });
+        for id in children.iter().chain(impl_items.flatten()) {
+            let Some(item) = self.krate.index.get(id) else { continue };
+            match &item.inner {
+                ItemEnum::Import(import) => {
+                    let Some(target) = import.id.as_ref().and_then(|id| self.krate.index.get(id))
+                    else {
+                        continue;
+                    };
+                    if import.glob {
+                        if let Some(item) = self.child(target, name) {
+                            return Some(item);
+                        }
+                    } else if import.name == name {
+                        return Some(target);
+                    }
+                }
+                _ if item.name.as_deref() == Some(name) => return Some(item),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The whole deserialized JSON output.
+    pub fn krate(&self) -> &Crate {
+        &self.krate
+    }
+}
@@ -0,0 +1,226 @@
+//! Reading the search index rustdoc writes to `search-index{suffix}.js`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Kind of an item in the search index, numbered like rustdoc's `ItemType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Keyword,
+    Primitive,
+    Module,
+    ExternCrate,
+    Import,
+    Struct,
+    Enum,
+    Function,
+    TypeAlias,
+    Static,
+    Trait,
+    Impl,
+    TyMethod,
+    Method,
+    StructField,
+    Variant,
+    Macro,
+    AssocType,
+    Constant,
+    AssocConst,
+    Union,
+    ForeignType,
+    OpaqueTy,
+    ProcAttribute,
+    ProcDerive,
+    TraitAlias,
+}
+
+impl ItemKind {
+    const ALL: [ItemKind; 26] = [
+        ItemKind::Keyword,
+        ItemKind::Primitive,
+        ItemKind::Module,
+        ItemKind::ExternCrate,
+        ItemKind::Import,
+        ItemKind::Struct,
+        ItemKind::Enum,
+        ItemKind::Function,
+        ItemKind::TypeAlias,
+        ItemKind::Static,
+        ItemKind::Trait,
+        ItemKind::Impl,
+        ItemKind::TyMethod,
+        ItemKind::Method,
+        ItemKind::StructField,
+        ItemKind::Variant,
+        ItemKind::Macro,
+        ItemKind::AssocType,
+        ItemKind::Constant,
+        ItemKind::AssocConst,
+        ItemKind::Union,
+        ItemKind::ForeignType,
+        ItemKind::OpaqueTy,
+        ItemKind::ProcAttribute,
+        ItemKind::ProcDerive,
+        ItemKind::TraitAlias,
+    ];
+
+    /// Decode the one character type code of the `t` field, `'A'` being the first kind.
+    fn from_code(code: char) -> Option<Self> {
+        let index = (code as u32).checked_sub('A' as u32)?;
+        Self::ALL.get(index as usize).copied()
+    }
+}
+
+/// An item in the search index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchEntry {
+    /// The crate the item was indexed for.
+    pub crate_name: String,
+    pub name: String,
+    /// The module path of the item, like `foo::bar`.
+    pub path: String,
+    pub kind: ItemKind,
+    /// The name of the parent item of methods, fields, variants and the like.
+    pub parent: Option<String>,
+}
+
+/// The parsed search index of a documentation directory.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    entries: Vec<SearchEntry>,
+}
+
+/// Locate the `search-index*.js` file in `out_dir`, whatever its resource suffix, and parse it.
+#[track_caller]
+pub fn read_search_index<P: AsRef<Path>>(out_dir: P) -> SearchIndex {
+    let out_dir = out_dir.as_ref();
+    let files: Vec<_> = fs::read_dir(out_dir)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", out_dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("search-index") && name.ends_with(".js")
+        })
+        .collect();
+    let [file] = &files[..] else {
+        panic!("expected exactly one search index in `{}`, found {files:?}", out_dir.display());
+    };
+    let js = fs::read_to_string(file).unwrap();
+    SearchIndex::parse(&js)
+        .unwrap_or_else(|| panic!("failed to parse search index `{}`", file.display()))
+}
+
+impl SearchIndex {
+    /// Parse the contents of a `search-index.js` file, which embeds the index as JSON in a JS
+    /// string literal: `var searchIndex = new Map(JSON.parse('...'));`.
+    fn parse(js: &str) -> Option<Self> {
+        let start = js.find("JSON.parse('")? + "JSON.parse('".len();
+        let end = start + js[start..].find("'));")?;
+        let json: Value = serde_json::from_str(&unescape_js_string(&js[start..end])).ok()?;
+
+        let mut entries = Vec::new();
+        for krate in json.as_array()? {
+            let [crate_name, data] = &krate.as_array()?[..] else { return None };
+            let crate_name = crate_name.as_str()?;
+            let types: Vec<char> = data.get("t")?.as_str()?.chars().collect();
+            let names = data.get("n")?.as_array()?;
+            let parents = data.get("i")?.as_array()?;
+            let parent_paths = data.get("p")?.as_array()?;
+            // Only the items where the path changes are listed, the others use the path of the
+            // item before them.
+            let mut paths = HashMap::new();
+            for path in data.get("q")?.as_array()? {
+                let [index, path] = &path.as_array()?[..] else { return None };
+                paths.insert(index.as_u64()?, path.as_str()?);
+            }
+
+            let mut last_path = crate_name;
+            for (i, (&code, name)) in types.iter().zip(names).enumerate() {
+                let path = paths.get(&(i as u64)).copied().unwrap_or(last_path);
+                last_path = path;
+                let parent = match parents.get(i).and_then(Value::as_u64) {
+                    Some(index) if index > 0 => {
+                        Some(parent_paths.get(index as usize - 1)?.get(1)?.as_str()?.to_string())
+                    }
+                    _ => None,
+                };
+                entries.push(SearchEntry {
+                    crate_name: crate_name.to_string(),
+                    name: name.as_str()?.to_string(),
+                    path: path.to_string(),
+                    kind: ItemKind::from_code(code)?,
+                    parent,
+                });
+            }
+        }
+        Some(SearchIndex { entries })
+    }
+
+    /// Whether an item called `name` is indexed.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.name == name)
+    }
+
+    /// The kind of the first item indexed as `name`, which can be qualified by the trailing
+    /// segments of its path, like `Bar::method`, `inner::helper` or `foo::Bar::method`.
+    pub fn kind_of(&self, name: &str) -> Option<ItemKind> {
+        self.matching(name).next().map(|entry| entry.kind)
+    }
+
+    /// Assert that an item indexed as `name`, qualified like for [`SearchIndex::kind_of`], has the
+    /// kind `kind`.
+    #[track_caller]
+    pub fn assert_indexed_as(&self, name: &str, kind: ItemKind) {
+        let kinds: Vec<_> = self.matching(name).map(|entry| entry.kind).collect();
+        if kinds.is_empty() {
+            panic!("`{name}` is not in the search index");
+        }
+        if !kinds.contains(&kind) {
+            panic!("`{name}` is indexed as {kinds:?}, not as {kind:?}");
+        }
+    }
+
+    /// The items indexed as `name`, see [`SearchIndex::kind_of`].
+    fn matching<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a SearchEntry> {
+        let (qualifier, name) = match name.rsplit_once("::") {
+            Some((qualifier, name)) => (Some(qualifier), name),
+            None => (None, name),
+        };
+        self.entries.iter().filter(move |entry| {
+            if entry.name != name {
+                return false;
+            }
+            let Some(qualifier) = qualifier else { return true };
+            let parent_path = match &entry.parent {
+                Some(parent) => format!("{}::{parent}", entry.path),
+                None => entry.path.clone(),
+            };
+            parent_path == qualifier || parent_path.ends_with(&format!("::{qualifier}"))
+        })
+    }
+
+    /// All the indexed items, in index order.
+    pub fn entries(&self) -> impl Iterator<Item = &SearchEntry> {
+        self.entries.iter()
+    }
+}
+
+/// Undo the escaping of a JS single-quoted string literal: backslash-newline line continuations
+/// are dropped and any other escaped character is taken literally.
+fn unescape_js_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(escaped) => result.push(escaped),
+            },
+            _ => result.push(c),
+        }
+    }
+    result
+}
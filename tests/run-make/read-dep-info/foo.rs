@@ -0,0 +1,5 @@
+//! Crate documentation.
+
+#![doc = include_str!("extra docs.md")]
+
+pub fn foo() {}
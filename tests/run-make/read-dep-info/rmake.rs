@@ -0,0 +1,18 @@
+// Check that `read_dep_info` reads back the source files listed in the dep file rustc writes for a
+// crate, including an included one whose name has an escaped space.
+
+extern crate run_make_support;
+
+use run_make_support::{read_dep_info, rustc, tmp_dir};
+use std::path::PathBuf;
+
+fn main() {
+    let dep_file = tmp_dir().join("foo.d");
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .emit(&format!("dep-info={}", dep_file.display()))
+        .run();
+    let inputs = read_dep_info(&dep_file);
+    assert_eq!(inputs, [PathBuf::from("foo.rs"), PathBuf::from("extra docs.md")]);
+}
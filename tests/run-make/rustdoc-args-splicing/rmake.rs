@@ -0,0 +1,31 @@
+// Check that `args` splices any iterator of arguments, and that `arg_if` only adds its argument
+// when the condition holds.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let flags = vec![String::from("--crate-name"), String::from("spliced")];
+    for document_private in [false, true] {
+        let out_dir = tmp_dir().join(format!("private-{document_private}"));
+        rustdoc()
+            .input("foo.rs")
+            .out_dir(&out_dir)
+            .args(flags.clone())
+            .arg_if(document_private, "--document-private-items")
+            .arg_if(false, "--this-flag-does-not-exist")
+            .inspect(|cmd| {
+                let args: Vec<_> =
+                    cmd.get_args().skip_while(|&arg| arg != "--crate-name").collect();
+                let mut expected = vec!["--crate-name", "spliced"];
+                if document_private {
+                    expected.push("--document-private-items");
+                }
+                assert_eq!(args, expected);
+            })
+            .run();
+        assert!(out_dir.join("spliced/fn.foo.html").exists());
+        assert_eq!(out_dir.join("spliced/fn.private.html").exists(), document_private);
+    }
+}
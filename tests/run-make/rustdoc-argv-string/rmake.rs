@@ -0,0 +1,35 @@
+// Check that `to_argv_string` quotes the arguments so that a shell splits the line back into the
+// exact program and arguments of the command.
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::process::Command;
+
+fn main() {
+    let mut builder = rustdoc();
+    builder
+        .input("foo.rs")
+        .crate_name("foo")
+        .out_dir(tmp_dir().join("dir with spaces"))
+        .crate_version("it's $HOME");
+    builder.print_command();
+    let line = builder.to_argv_string();
+    assert!(line.contains("'"), "{line}");
+
+    let printf = format!("printf '%s\\n' {line}");
+    let output = Command::new("sh").arg("-c").arg(printf).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let words: Vec<_> = stdout.lines().collect();
+    builder.inspect(|cmd| {
+        let expected: Vec<_> = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|word| word.to_str().unwrap())
+            .collect();
+        assert_eq!(words, expected);
+    });
+    builder.run();
+    assert!(tmp_dir().join("dir with spaces/foo/fn.foo.html").exists());
+}
@@ -0,0 +1,2 @@
+// ignore-tidy-linelength
+"use strict";function greet(name){return"Hello, "+name+"!"}function f0(a,b){return a*0+b}function f1(a,b){return a*1+b}function f2(a,b){return a*2+b}function f3(a,b){return a*3+b}function f4(a,b){return a*4+b}function f5(a,b){return a*5+b}function f6(a,b){return a*6+b}function f7(a,b){return a*7+b}function f8(a,b){return a*8+b}function f9(a,b){return a*9+b}function f10(a,b){return a*10+b}function f11(a,b){return a*11+b}
@@ -0,0 +1,17 @@
+// Check that `assert_asset_readable` tells readable assets from minified ones, using a readable
+// and a minified fixture as well as the minified `main.js` rustdoc writes.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_asset_readable, resolved_asset, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    assert_asset_readable("readable.js");
+    assert!(panic::catch_unwind(|| assert_asset_readable("minified.js")).is_err());
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+    let main_js = resolved_asset(&out_dir, "main.js", "");
+    assert!(panic::catch_unwind(|| assert_asset_readable(&main_js)).is_err());
+}
@@ -0,0 +1,7 @@
+/// ```
+/// let _ = dep::Dep;
+/// let pid_file = std::env::var("RUSTDOC_DOCTEST_PID_FILE").unwrap();
+/// std::fs::write(pid_file, std::process::id().to_string()).unwrap();
+/// std::thread::sleep(std::time::Duration::from_secs(600));
+/// ```
+pub fn hang() {}
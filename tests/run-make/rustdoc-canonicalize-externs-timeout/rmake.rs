@@ -0,0 +1,43 @@
+// Check that `timeout` still kills the doctests along with rustdoc when the command is rebuilt to
+// canonicalize the `--extern` paths, by keeping rustdoc in a process group of its own.
+//@ only-linux
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, rustdoc, tmp_dir};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+/// Whether the process `pid` is still running, as opposed to gone or only waiting to be reaped.
+fn is_running(pid: &str) -> bool {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{pid}/stat")) else { return false };
+    let state = stat.rsplit_once(") ").map(|(_, rest)| &rest[..1]);
+    state != Some("Z") && state != Some("X")
+}
+
+fn main() {
+    let input = env::current_dir().unwrap().join("hang.rs");
+    rustc().input("dep.rs").crate_type("lib").run();
+    let pid_file = tmp_dir().join("doctest.pid");
+    env::set_current_dir(tmp_dir()).unwrap();
+
+    let hanging = rustdoc()
+        .input(&input)
+        .edition("2021")
+        .arg("--test")
+        .extern_("dep", "libdep.rlib")
+        .canonicalize_externs()
+        .env("RUSTDOC_DOCTEST_PID_FILE", &pid_file)
+        .timeout(Duration::from_secs(20));
+    assert!(hanging.timed_out());
+
+    assert!(Path::new(&pid_file).exists(), "the doctest didn't start before the timeout");
+    let pid = fs::read_to_string(&pid_file).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while is_running(&pid) {
+        assert!(Instant::now() < deadline, "the doctest {pid} survived the timeout");
+        thread::sleep(Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,27 @@
+// Check that `canonicalize_externs` resolves a relative `--extern` path from the directory the
+// test runs in rather than from rustdoc's `current_dir`, and that a missing path panics.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, rustdoc, tmp_dir};
+use std::{env, fs, panic};
+
+fn main() {
+    let input = env::current_dir().unwrap().join("foo.rs");
+    rustc().input("dep.rs").crate_type("lib").run();
+    let subdir = tmp_dir().join("subdir");
+    fs::create_dir(&subdir).unwrap();
+    env::set_current_dir(tmp_dir()).unwrap();
+
+    let mut base = rustdoc();
+    base.input(&input).edition("2021").extern_("dep", "libdep.rlib").current_dir(&subdir);
+    base.clone().out_dir(tmp_dir().join("relative")).run_fail_with_code(1);
+
+    base.clone().out_dir(tmp_dir().join("canonical")).canonicalize_externs().run();
+    assert!(tmp_dir().join("canonical/foo/struct.Dep.html").exists());
+
+    let missing = panic::catch_unwind(|| {
+        rustdoc().input(&input).extern_("dep", "libmissing.rlib").canonicalize_externs().run();
+    });
+    assert!(missing.is_err());
+}
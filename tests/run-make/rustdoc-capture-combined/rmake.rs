@@ -0,0 +1,41 @@
+// Check that `capture_combined` keeps the order in which rustdoc and its doctests write to stdout
+// and stderr, both when waiting for rustdoc and when running it with a timeout.
+//@ ignore-windows `timeout` is only available on unix
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, CompletedProcess};
+use std::time::Duration;
+
+fn assert_in_order(process: &CompletedProcess) {
+    let combined = process.combined_utf8();
+    let positions: Vec<_> = [
+        "running 1 test",
+        "first, on stderr",
+        "second, on stdout",
+        "third, on stderr",
+        "test result: ok",
+    ]
+    .iter()
+    .map(|line| combined.find(line).unwrap_or_else(|| panic!("`{line}` missing:\n{combined}")))
+    .collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "out of order:\n{combined}");
+    assert!(process.stderr_utf8().is_empty());
+}
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.input("streams.rs").arg("--test").args(["-Z", "unstable-options", "--nocapture"]);
+
+    let separate = doc.run_and_capture();
+    separate.assert_stdout_contains("second, on stdout").assert_stderr_contains("first, on stderr");
+
+    doc.capture_combined();
+    let combined = doc.run_and_capture();
+    combined.assert_exit_code(0);
+    assert_in_order(&combined);
+
+    let timed = doc.timeout(Duration::from_secs(60));
+    assert!(!timed.timed_out());
+    assert_in_order(&timed);
+}
@@ -0,0 +1,10 @@
+/// ```
+/// use std::io::Write;
+///
+/// eprintln!("first, on stderr");
+/// std::io::stderr().flush().unwrap();
+/// println!("second, on stdout");
+/// std::io::stdout().flush().unwrap();
+/// eprintln!("third, on stderr");
+/// ```
+pub fn streams() {}
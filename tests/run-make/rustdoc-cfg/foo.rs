@@ -0,0 +1,8 @@
+#[cfg(feature_a)]
+pub fn feature_a() {}
+
+#[cfg(feature_b)]
+pub fn feature_b() {}
+
+#[cfg(feature_c)]
+pub fn feature_c() {}
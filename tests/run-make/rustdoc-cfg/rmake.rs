@@ -0,0 +1,25 @@
+// Check that `cfg` and `cfgs` pass one `--cfg` per cfg in order, skipping repeated ones, and
+// that the items behind those cfgs get documented.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+
+fn main() {
+    let mut single = rustdoc();
+    single.cfg("feature_a");
+    let args: Vec<_> = single.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--cfg", "feature_a"].map(OsStr::new)));
+
+    let mut several = rustdoc();
+    several.cfgs(["feature_a", "feature_b"]).cfg("feature_a").cfgs(["feature_b"]);
+    let args: Vec<_> = several.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--cfg", "feature_a", "--cfg", "feature_b"].map(OsStr::new)));
+    assert_eq!(args.iter().filter(|arg| **arg == "--cfg").count(), 2);
+
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).cfgs(["feature_a", "feature_b"]).run();
+    assert!(tmp_dir().join("foo/fn.feature_a.html").exists());
+    assert!(tmp_dir().join("foo/fn.feature_b.html").exists());
+    assert!(!tmp_dir().join("foo/fn.feature_c.html").exists());
+}
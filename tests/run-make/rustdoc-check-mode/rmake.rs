@@ -0,0 +1,16 @@
+// Check that `--check` mode surfaces diagnostics without writing any docs.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let diagnostics = rustdoc().input("foo.rs").out_dir(&out_dir).run_check();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diag| diag.code.as_deref() == Some("rustdoc::broken_intra_doc_links"))
+    );
+    assert!(!out_dir.exists());
+}
@@ -0,0 +1,26 @@
+// Check that `check_theme` reports what an incomplete theme lacks, and nothing for the variables
+// of the light theme rustdoc itself ships. rustdoc never reports extra rules.
+
+extern crate run_make_support;
+
+use run_make_support::{check_theme, tmp_dir};
+use std::{env, fs};
+
+fn main() {
+    let incomplete = check_theme("incomplete.css");
+    assert!(incomplete.missing.iter().any(|variable| variable == "--link-color"));
+    assert!(incomplete.missing.iter().all(|variable| variable != "--main-color"));
+    assert!(incomplete.extra.is_empty());
+
+    let noscript = fs::read_to_string(
+        env::var("S").unwrap() + "/src/librustdoc/html/static/css/noscript.css",
+    )
+    .unwrap();
+    let light = noscript.split_once("/* Begin theme: light */").unwrap().1;
+    let light = light.split_once("/* End theme: light */").unwrap().0;
+    // The theme applies to more selectors than `:root`, which is all a custom theme needs.
+    let variables = light.split_once('{').unwrap().1;
+    let complete = tmp_dir().join("light.css");
+    fs::write(&complete, format!(":root {{{variables}")).unwrap();
+    assert_eq!(check_theme(&complete).missing, Vec::<String>::new());
+}
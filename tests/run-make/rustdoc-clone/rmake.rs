@@ -0,0 +1,46 @@
+// Check that a cloned `Rustdoc` builder is independent from the original: each clone gets its own
+// edition, and neither affects the base invocation. A clone of an invocation with a cleared
+// environment runs with a cleared environment too, keeping the variables set afterwards.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::env;
+
+fn main() {
+    let mut base = rustdoc();
+    base.input("lib.rs").out_dir(tmp_dir()).env("RUSTDOC_CLONE_VAR", "1");
+
+    let mut edition2015 = base.clone();
+    edition2015.edition("2015");
+    let mut edition2018 = base.clone();
+    edition2018.edition("2018");
+
+    edition2015
+        .run_fail_with_code(1)
+        .assert_stderr_contains("`async fn` is not permitted in Rust 2015");
+    edition2018.run();
+
+    base.inspect(|cmd| {
+        assert!(!cmd.get_args().any(|arg| arg == "--edition"));
+    });
+    edition2018.inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args.iter().filter(|&&arg| arg == "--edition").count(), 1);
+        assert!(cmd.get_envs().any(|(key, _)| key == "RUSTDOC_CLONE_VAR"));
+    });
+
+    env::set_var("RUSTDOC_CLONE_INHERITED", "1");
+    let mut inheriting = rustdoc();
+    inheriting.input("env.rs").out_dir(tmp_dir());
+    inheriting.clone().run();
+
+    let mut cleared = inheriting.clone();
+    cleared.env_clear();
+    cleared
+        .clone()
+        .run_fail_with_code(1)
+        .assert_stderr_contains("environment variable `RUSTDOC_CLONE_INHERITED` not defined");
+    cleared.env("RUSTDOC_CLONE_INHERITED", "1");
+    cleared.clone().run();
+}
@@ -0,0 +1,20 @@
+// Check that `codegen` passes `-C {opt}` once per call, and that `codegen_kv` passes
+// `-C {key}={value}`.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.codegen("debug-assertions").codegen("overflow-checks=off");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = ["-C", "debug-assertions", "-C", "overflow-checks=off"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let mut doc = rustdoc();
+    doc.codegen_kv("panic", "abort");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    assert!(args.ends_with(&["-C", "panic=abort"].map(OsStr::new)));
+}
@@ -0,0 +1,27 @@
+// Check that `--color=always` makes rustdoc emit ANSI escape sequences in its diagnostics and
+// that `--color=never` doesn't.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, ColorChoice, ErrorFormat};
+
+fn main() {
+    let output = rustdoc()
+        .input("warning.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Human)
+        .color(ColorChoice::Always)
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("\x1b["), "expected escape sequences in:\n{stderr}");
+
+    let output = rustdoc()
+        .input("warning.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Human)
+        .color(ColorChoice::Never)
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("DoesNotExist"));
+    assert!(!stderr.contains('\x1b'), "unexpected escape sequences in:\n{stderr}");
+}
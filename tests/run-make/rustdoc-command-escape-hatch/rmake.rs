@@ -0,0 +1,24 @@
+// Check that `command_mut` lets a test push raw arguments between builder methods, and that
+// `to_command` hands over a command which still runs rustdoc.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let mut builder = rustdoc();
+    builder.input("foo.rs");
+    builder.command_mut().arg("--crate-name").arg("raw");
+    builder.out_dir(&out_dir).inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().skip_while(|&arg| arg != "foo.rs").collect();
+        let expected = ["foo.rs", "--crate-name", "raw", "--out-dir"].map(OsStr::new);
+        assert_eq!(args[..4], expected);
+        assert_eq!(args[4], out_dir);
+    });
+
+    let output = builder.to_command().output().unwrap();
+    assert!(output.status.success());
+    assert!(out_dir.join("raw/fn.foo.html").exists());
+}
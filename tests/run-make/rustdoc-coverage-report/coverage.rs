@@ -0,0 +1,15 @@
+//! A partially documented crate.
+
+/// This one is documented.
+pub fn documented() {}
+
+pub fn undocumented() {}
+
+/// This one is documented and has an example.
+///
+/// ```
+/// coverage::with_example();
+/// ```
+pub fn with_example() {}
+
+pub struct Undocumented;
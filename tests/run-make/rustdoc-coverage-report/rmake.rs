@@ -0,0 +1,24 @@
+// Check that `run_coverage` parses the JSON documentation coverage report of a partially
+// documented crate.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+
+fn main() {
+    let report = rustdoc().input("coverage.rs").run_coverage();
+    assert_eq!(report.files.len(), 1);
+    let file = &report.files[0];
+    assert!(file.file.ends_with("coverage.rs"));
+
+    // The crate root and two of the four items are documented.
+    assert_eq!(file.total, 5);
+    assert_eq!(file.with_docs, 3);
+    assert_eq!(file.percentage(), Some(60.0));
+    assert_eq!(file.with_examples, 1);
+
+    assert_eq!(report.total.file, "Total");
+    assert_eq!(report.total.total, file.total);
+    assert_eq!(report.total.with_docs, file.with_docs);
+    assert_eq!(report.total.examples_percentage(), file.examples_percentage());
+}
@@ -0,0 +1,29 @@
+// Check that `crate_name` and `try_crate_name` reject names rustdoc would, like package names
+// with hyphens or dotted names, before running anything.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, CrateNameError};
+use std::panic;
+
+fn main() {
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).crate_name("valid_name2").run();
+    assert!(tmp_dir().join("valid_name2/struct.Foo.html").exists());
+
+    let hyphenated = rustdoc().try_crate_name("my-crate").map(|_| ()).unwrap_err();
+    assert_eq!(
+        hyphenated,
+        CrateNameError { crate_name: "my-crate".to_string(), character: Some('-') }
+    );
+    assert!(hyphenated.to_string().contains("use `_` instead"));
+
+    let dotted = rustdoc().try_crate_name("my.crate").map(|_| ()).unwrap_err();
+    assert_eq!(dotted.character, Some('.'));
+    assert_eq!(rustdoc().try_crate_name("2fast").map(|_| ()).unwrap_err().character, Some('2'));
+    assert_eq!(rustdoc().try_crate_name("").map(|_| ()).unwrap_err().character, None);
+
+    let panicked = panic::catch_unwind(|| {
+        rustdoc().crate_name("my-crate");
+    });
+    assert!(panicked.is_err());
+}
@@ -0,0 +1,33 @@
+// Check that `crate_types` joins the kinds into a single deduplicated `--crate-type`, and that
+// `crate_type` validates each comma-separated kind.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, CrateType};
+use std::ffi::OsStr;
+use std::panic;
+
+fn crate_type_args(kinds: &[CrateType]) -> Vec<String> {
+    let mut doc = rustdoc();
+    doc.crate_types(kinds);
+    doc.command_mut().get_args().map(|arg| arg.to_str().unwrap().to_owned()).collect()
+}
+
+fn main() {
+    assert!(crate_type_args(&[CrateType::Lib]).ends_with(&["--crate-type".into(), "lib".into()]));
+    let several = crate_type_args(&[CrateType::Rlib, CrateType::ProcMacro, CrateType::Rlib]);
+    assert!(several.ends_with(&["--crate-type".into(), "rlib,proc-macro".into()]));
+
+    let mut by_name = rustdoc();
+    by_name.crate_type("lib,dylib,lib");
+    let args: Vec<_> = by_name.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--crate-type", "lib,dylib"].map(OsStr::new)));
+
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).crate_types(&[CrateType::Lib]).run();
+    assert!(tmp_dir().join("foo/fn.foo.html").exists());
+
+    let unknown = panic::catch_unwind(|| {
+        rustdoc().crate_type("lib,library");
+    });
+    assert!(unknown.is_err());
+}
@@ -0,0 +1,11 @@
+// Check that `current_dir` sets the working directory of rustdoc: the input is found relative to
+// it, and so is the file the doctest reads at runtime.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+
+fn main() {
+    let summary = rustdoc().current_dir("dir").input("lib.rs").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+}
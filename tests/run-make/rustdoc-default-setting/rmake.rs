@@ -0,0 +1,35 @@
+// Check that `default_setting` passes one `--default-setting name=value` per call, that the
+// settings end up on the pages, and that names which are empty or contain `=` are rejected.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_xpath_attr, rustdoc, tmp_dir};
+use std::ffi::OsStr;
+use std::panic;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.default_setting("line-numbers", "true").default_setting("theme", "ayu");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = ["--default-setting", "line-numbers=true", "--default-setting", "theme=ayu"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+    assert!(args.contains(&OsStr::new("unstable-options")));
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&out_dir)
+        .default_setting("line-numbers", "true")
+        .default_setting("theme", "ayu")
+        .run();
+    let page = out_dir.join("foo/fn.foo.html");
+    assert_xpath_attr(&page, "//script[@id='default-settings']", "data-theme", "ayu");
+    assert_xpath_attr(&page, "//script[@id='default-settings']", "data-line_numbers", "true");
+
+    for name in ["theme=dark", ""] {
+        let malformed = panic::catch_unwind(|| {
+            rustdoc().default_setting(name, "ayu");
+        });
+        assert!(malformed.is_err(), "`{name}` was accepted");
+    }
+}
@@ -0,0 +1,6 @@
+//! Crate.
+
+/// Links to [`Missing`].
+pub struct Foo;
+
+pub fn undocumented() {}
@@ -0,0 +1,25 @@
+// Check that `assert_has_diagnostic_code` finds the code of an error or a lint among the JSON
+// diagnostics, skipping the ones without a code, and panics when it is absent.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, ErrorFormat};
+use std::panic;
+
+fn main() {
+    let lints = rustdoc()
+        .input("lints.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Json)
+        .run_and_capture();
+    lints.assert_has_diagnostic_code("rustdoc::broken_intra_doc_links");
+    let missing = panic::catch_unwind(|| lints.assert_has_diagnostic_code("missing_docs"));
+    assert!(missing.is_err());
+
+    rustdoc()
+        .input("error.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Json)
+        .run_fail_with_code(1)
+        .assert_has_diagnostic_code("E0428");
+}
@@ -0,0 +1,3 @@
+// ignore-tidy-linelength
+/// Links to [`Missing`] ........................................................................................................ end.
+pub struct Foo;
@@ -0,0 +1,31 @@
+// Check that `diagnostic_width` makes rustdoc trim the source snippets of its diagnostics to the
+// given width.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, ColorChoice};
+
+fn snippet(stderr: &str) -> &str {
+    stderr.lines().find(|line| line.starts_with("2 | ")).unwrap()
+}
+
+fn main() {
+    let wide = rustdoc()
+        .input("long-line.rs")
+        .out_dir(tmp_dir())
+        .color(ColorChoice::Never)
+        .diagnostic_width(200)
+        .run_and_capture()
+        .stderr_utf8();
+    assert!(snippet(&wide).ends_with("end."));
+
+    let narrow = rustdoc()
+        .input("long-line.rs")
+        .out_dir(tmp_dir())
+        .color(ColorChoice::Never)
+        .diagnostic_width(40)
+        .run_and_capture()
+        .stderr_utf8();
+    let snippet = snippet(&narrow);
+    assert!(snippet.starts_with("2 | ...") && snippet.chars().count() <= 40, "{snippet}");
+}
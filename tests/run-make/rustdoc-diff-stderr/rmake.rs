@@ -0,0 +1,35 @@
+// Check that `run_and_diff_stderr` accepts a stderr matching the golden file once normalized,
+// fails with a diff when it doesn't match, and blesses the golden file with `RUSTC_BLESS_TEST`.
+// `$DIR` stands for the working directory of rustdoc, even when it is set within `TMPDIR`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::{env, fs, panic};
+
+fn main() {
+    env::remove_var("RUSTC_BLESS_TEST");
+    let input = env::current_dir().unwrap().join("foo.rs");
+    rustdoc().input(&input).out_dir(tmp_dir()).run_and_diff_stderr("foo.stderr");
+
+    let work_dir = tmp_dir().join("work");
+    fs::create_dir_all(&work_dir).unwrap();
+    fs::copy(&input, work_dir.join("foo.rs")).unwrap();
+    rustdoc()
+        .current_dir(&work_dir)
+        .input(work_dir.join("foo.rs"))
+        .out_dir(tmp_dir())
+        .run_and_diff_stderr(env::current_dir().unwrap().join("foo.stderr"));
+
+    let mismatch = panic::catch_unwind(|| {
+        rustdoc().input(&input).out_dir(tmp_dir()).run_and_diff_stderr("other.stderr");
+    });
+    let message = *mismatch.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("-warning: this is not the expected warning"), "{message}");
+    assert!(message.contains("+warning: unresolved link to `Missing`"), "{message}");
+
+    let blessed = tmp_dir().join("blessed.stderr");
+    env::set_var("RUSTC_BLESS_TEST", "1");
+    rustdoc().input(&input).out_dir(tmp_dir()).run_and_diff_stderr(&blessed);
+    assert_eq!(fs::read_to_string(blessed).unwrap(), fs::read_to_string("foo.stderr").unwrap());
+}
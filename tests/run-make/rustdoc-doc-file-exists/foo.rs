@@ -0,0 +1,8 @@
+pub fn g() {}
+
+pub mod inner {
+    pub struct Visible;
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
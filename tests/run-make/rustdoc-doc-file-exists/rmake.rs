@@ -0,0 +1,22 @@
+// Check that `assert_doc_file_exists` and `assert_doc_file_missing` find the pages of a crate,
+// including the ones of nested modules, and panic when the expectation doesn't hold.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_doc_file_exists, assert_doc_file_missing, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+
+    assert_doc_file_exists(&out_dir, "foo", "fn.g.html");
+    assert_doc_file_exists(&out_dir, "foo", "inner/struct.Visible.html");
+    assert_doc_file_missing(&out_dir, "foo", "fn.hidden.html");
+    assert_doc_file_missing(&out_dir, "foo", "missing/struct.Nothing.html");
+
+    let present = panic::catch_unwind(|| assert_doc_file_missing(&out_dir, "foo", "fn.g.html"));
+    assert!(present.is_err());
+    let absent = panic::catch_unwind(|| assert_doc_file_exists(&out_dir, "foo", "fn.hidden.html"));
+    assert!(absent.is_err());
+}
@@ -0,0 +1,5 @@
+/// ```
+/// let data = std::fs::read_to_string("data.txt").unwrap();
+/// assert_eq!(data.trim(), "found from the run directory");
+/// ```
+pub fn reads_data() {}
@@ -0,0 +1,36 @@
+// Check the flags passed by `no_run`, `test_run_directory` and `persist_doctests`, with a single
+// `-Z unstable-options` for the unstable ones, that doctests run from the directory given to
+// `test_run_directory`, and that `no_run` with `persist_doctests` keeps them without running them.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+use std::fs;
+
+fn main() {
+    let persist_dir = tmp_dir().join("persisted");
+    let mut doc = rustdoc();
+    doc.no_run().test_run_directory("run-dir").persist_doctests(&persist_dir);
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let persist_dir_arg = persist_dir.to_str().unwrap();
+    let expected = [
+        "-Z",
+        "unstable-options",
+        "--no-run",
+        "--test-run-directory",
+        "run-dir",
+        "--persist-doctests",
+        persist_dir_arg,
+    ];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+    assert_eq!(args.iter().filter(|arg| **arg == "unstable-options").count(), 1);
+
+    let summary = rustdoc().input("lib.rs").test_run_directory("run-dir").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+    let summary = rustdoc().input("lib.rs").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (0, 1));
+
+    rustdoc().input("lib.rs").arg("--test").no_run().persist_doctests(&persist_dir).run();
+    assert_eq!(fs::read_dir(&persist_dir).unwrap().count(), 1);
+}
@@ -0,0 +1,22 @@
+#![crate_name = "doctests"]
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+pub fn passes() {}
+
+/// ```
+/// let v = vec![1, 2, 3];
+/// assert_eq!(v.len(), 3);
+/// ```
+pub fn also_passes() {}
+
+/// ```
+/// panic!("this doctest fails on purpose");
+/// ```
+pub fn fails() {}
+
+/// ```ignore (not run)
+/// this is not rust
+/// ```
+pub fn ignored() {}
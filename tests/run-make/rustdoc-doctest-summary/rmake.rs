@@ -0,0 +1,20 @@
+// Check that `run_doctests` parses the libtest summary of `rustdoc --test`, including when some
+// of the doctests fail.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, DoctestSummary};
+
+fn main() {
+    let summary = rustdoc().input("doctests.rs").run_doctests();
+    assert_eq!(
+        summary,
+        DoctestSummary { passed: 2, failed: 1, ignored: 1, measured: 0, filtered_out: 0 }
+    );
+
+    let summary =
+        rustdoc().input("doctests.rs").arg("--test").arg("--test-args=passes").run_doctests();
+    assert_eq!(summary.passed, 2);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.filtered_out, 2);
+}
@@ -0,0 +1,24 @@
+// Check that `document_all` documents every `.rs` file of a directory, in sorted order and each
+// in its own output directory under the one configured on the base invocation.
+
+extern crate run_make_support;
+
+use run_make_support::{document_all, rustdoc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let mut base = rustdoc();
+    base.out_dir(&out_dir).edition("2021");
+    let outputs = document_all(base, "crates");
+    assert_eq!(outputs.len(), 3);
+    assert!(outputs.iter().all(|output| output.status().success()));
+
+    let mut doc_dirs: Vec<_> =
+        fs::read_dir(&out_dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    doc_dirs.sort();
+    assert_eq!(doc_dirs, ["alpha", "beta", "gamma"]);
+    for name in ["alpha", "beta", "gamma"] {
+        assert!(out_dir.join(name).join(name).join("index.html").exists());
+    }
+}
@@ -0,0 +1,21 @@
+// Check that `document` returns the directory of the crate's docs, in a new output directory
+// unless one was configured.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let first = rustdoc().document("my-lib.rs");
+    assert_eq!(first.file_name().unwrap(), "my_lib");
+    assert!(first.starts_with(tmp_dir()));
+    assert!(first.join("struct.Documented.html").exists());
+
+    let second = rustdoc().document("my-lib.rs");
+    assert_ne!(first, second);
+
+    let out_dir = tmp_dir().join("configured");
+    let named = rustdoc().out_dir(&out_dir).crate_name("renamed").document("my-lib.rs");
+    assert_eq!(named, out_dir.join("renamed"));
+    assert!(named.join("struct.Documented.html").exists());
+}
@@ -0,0 +1,35 @@
+// Check that `emit` passes the kinds joined in a single `--emit` with `-Z unstable-options`
+// added once, and that rustdoc accepts each of them, only writing the crate pages for
+// `invocation-specific`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, Emit};
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.emit(&[Emit::InvocationSpecific, Emit::ToolchainSharedResources, Emit::InvocationSpecific]);
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected =
+        ["-Z", "unstable-options", "--emit=invocation-specific,toolchain-shared-resources"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+    assert_eq!(args.iter().filter(|&&arg| arg == "unstable-options").count(), 1);
+
+    let kinds = [
+        (Emit::UnversionedSharedResources, "unversioned-shared-resources"),
+        (Emit::ToolchainSharedResources, "toolchain-shared-resources"),
+        (Emit::InvocationSpecific, "invocation-specific"),
+    ];
+    for (kind, name) in kinds {
+        let out_dir = tmp_dir().join(name);
+        let mut doc = rustdoc();
+        doc.input("foo.rs").out_dir(&out_dir).emit(&[kind]);
+        let args: Vec<_> = doc.command_mut().get_args().collect();
+        assert!(args.contains(&OsStr::new(&format!("--emit={name}"))));
+        assert_eq!(args.iter().filter(|&&arg| arg == "unstable-options").count(), 1);
+        doc.run();
+    }
+    assert!(tmp_dir().join("invocation-specific/foo/index.html").exists());
+    assert!(!tmp_dir().join("toolchain-shared-resources/foo/index.html").exists());
+}
@@ -0,0 +1,4 @@
+/// ```
+/// assert!(std::env::var_os("RUSTDOC_REMOVED_VAR").is_none());
+/// ```
+pub fn foo() {}
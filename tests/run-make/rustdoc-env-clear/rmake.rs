@@ -0,0 +1,21 @@
+// Check that `env_remove` hides a variable from rustdoc and its doctests, and that rustdoc still
+// runs with a cleared environment.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::env;
+
+fn main() {
+    env::set_var("RUSTDOC_REMOVED_VAR", "1");
+    rustdoc()
+        .input("lib.rs")
+        .arg("--test")
+        .run_fail_with_code(101)
+        .assert_stdout_contains("1 failed");
+    let summary = rustdoc().input("lib.rs").env_remove("RUSTDOC_REMOVED_VAR").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+
+    rustdoc().input("lib.rs").out_dir(tmp_dir()).env_clear().run();
+    assert!(tmp_dir().join("lib/fn.foo.html").exists());
+}
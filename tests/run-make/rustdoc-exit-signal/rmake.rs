@@ -0,0 +1,22 @@
+// Check that `exit_signal` and `assert_no_signal` tell a crash from a clean failure, with an
+// aborting program standing in for a crashing rustdoc.
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::{bin_name, rustc, rustdoc, tmp_dir, Rustdoc};
+use std::panic;
+
+fn main() {
+    rustc().input("abort.rs").run();
+    let crashed = Rustdoc::with_rustdoc(tmp_dir().join(bin_name("abort")))
+        .input("missing.rs")
+        .run_unchecked()
+        .unwrap_err();
+    assert_eq!(crashed.exit_signal(), Some(6));
+    assert!(panic::catch_unwind(|| crashed.assert_no_signal()).is_err());
+
+    let failed = rustdoc().input("missing.rs").run_unchecked().unwrap_err();
+    assert_eq!(failed.exit_signal(), None);
+    failed.assert_no_signal().assert_exit_code(1);
+}
@@ -0,0 +1,34 @@
+// Check that the rules passed with `extend_css` end up in the theme CSS, with or without a
+// resource suffix, and that `assert_css_contains` rejects CSS without them.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_css_contains, rustdoc, tmp_dir};
+use std::panic;
+
+const MARKER: &str = ".rustdoc-extend-css-marker { color: rebeccapurple; }";
+
+fn main() {
+    let out_dir = tmp_dir().join("extended");
+    rustdoc().input("foo.rs").out_dir(&out_dir).extend_css("extra.css").run();
+    assert_css_contains(&out_dir, MARKER);
+
+    let suffixed = tmp_dir().join("suffixed");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&suffixed)
+        .extend_css("extra.css")
+        .resource_suffix("-1")
+        .run();
+    assert!(suffixed.join("theme-1.css").exists());
+    assert_css_contains(&suffixed, MARKER);
+
+    let plain = tmp_dir().join("plain");
+    rustdoc().input("foo.rs").out_dir(&plain).run();
+    assert!(panic::catch_unwind(|| assert_css_contains(&plain, MARKER)).is_err());
+
+    let missing = panic::catch_unwind(|| {
+        rustdoc().extend_css("missing.css");
+    });
+    assert!(missing.is_err());
+}
@@ -0,0 +1,35 @@
+// Check that `extern_from_map` adds one `--extern` per dependency, sorted by crate name, and
+// rejects invalid crate names like `extern_`.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::collections::BTreeMap;
+use std::panic;
+
+fn main() {
+    let mut deps = BTreeMap::new();
+    deps.insert(String::from("zeta"), "libzeta.rlib");
+    deps.insert(String::from("alpha"), "libalpha.rlib");
+    deps.insert(String::from("mid"), "libmid.rlib");
+    rustdoc().extern_from_map(&deps).inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().skip_while(|&arg| arg != "--extern").collect();
+        assert_eq!(
+            args,
+            [
+                "--extern",
+                "alpha=libalpha.rlib",
+                "--extern",
+                "mid=libmid.rlib",
+                "--extern",
+                "zeta=libzeta.rlib"
+            ]
+        );
+    });
+
+    deps.insert(String::from("not valid"), "libinvalid.rlib");
+    let invalid = panic::catch_unwind(|| {
+        rustdoc().extern_from_map(&deps);
+    });
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,3 @@
+extern crate dep;
+
+pub fn take(_: dep::Dep) {}
@@ -0,0 +1,45 @@
+// Check that `extern_html_root_url` passes `{crate_name}={url}` and that rustdoc links the items
+// of that crate to `url`, that `-Z unstable-options` is only added once alongside
+// `extern_html_root_takes_precedence`, and that invalid crate names are rejected like `extern_`.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_has_xpath, rustc, rustdoc, tmp_dir};
+use std::ffi::OsStr;
+use std::panic;
+
+fn main() {
+    let url = "https://example.com/docs/";
+    let mut doc = rustdoc();
+    doc.extern_html_root_url("dep", url).extern_html_root_takes_precedence();
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = [
+        "-Z",
+        "unstable-options",
+        "--extern-html-root-url",
+        "dep=https://example.com/docs/",
+        "--extern-html-root-takes-precedence",
+    ];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+    assert_eq!(args.iter().filter(|&&arg| arg == "unstable-options").count(), 1);
+
+    rustc().input("dep.rs").crate_type("lib").run();
+    let out_dir = tmp_dir().join("doc");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&out_dir)
+        .library_search_path(tmp_dir())
+        .extern_("dep", tmp_dir().join("libdep.rlib"))
+        .extern_html_root_url("dep", url)
+        .extern_html_root_takes_precedence()
+        .run();
+    let page = out_dir.join("foo/fn.take.html");
+    assert_has_xpath(&page, "//a[@href='https://example.com/docs/dep/struct.Dep.html']");
+
+    for crate_name in ["not valid", "dep/sub", "dep\\sub"] {
+        let rejected = panic::catch_unwind(|| {
+            rustdoc().extern_html_root_url(crate_name, url);
+        });
+        assert!(rejected.is_err(), "`{crate_name}` was accepted");
+    }
+}
@@ -0,0 +1,28 @@
+// Check that `force_warn` passes one `--force-warn` per lint, that `allow_all_lints` passes
+// `-A warnings`, and that the force-warned lints are the only ones still reported then.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, LintLevel};
+use std::ffi::OsStr;
+
+fn main() {
+    rustdoc().allow_all_lints().force_warn("missing_docs").force_warn("unused").inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().skip(1).collect();
+        let expected = ["-A", "warnings", "--force-warn", "missing_docs", "--force-warn", "unused"];
+        assert_eq!(args, expected.map(OsStr::new));
+    });
+
+    let quiet = rustdoc().input("lints.rs").out_dir(tmp_dir()).allow_all_lints().run_and_capture();
+    assert!(!quiet.stderr_utf8().contains("warning"), "{}", quiet.stderr_utf8());
+
+    rustdoc()
+        .input("lints.rs")
+        .out_dir(tmp_dir())
+        .allow_all_lints()
+        .cap_lints(LintLevel::Allow)
+        .force_warn("missing_docs")
+        .run_and_capture()
+        .assert_stderr_contains("missing documentation for a function")
+        .assert_stderr_contains("1 warning emitted");
+}
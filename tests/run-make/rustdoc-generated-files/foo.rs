@@ -0,0 +1,7 @@
+pub mod inner {
+    pub struct Bar;
+
+    pub fn baz() {}
+}
+
+pub trait Trait {}
@@ -0,0 +1,30 @@
+// Check that `assert_generated_files` accepts exactly the set of pages generated for a crate, and
+// reports the extra and missing ones otherwise.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_generated_files, rustdoc, tmp_dir};
+use std::panic;
+
+const PAGES: &[&str] = &[
+    "all.html",
+    "index.html",
+    "trait.Trait.html",
+    "inner/index.html",
+    "inner/struct.Bar.html",
+    "inner/fn.baz.html",
+];
+
+fn main() {
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).run();
+    assert_generated_files(tmp_dir(), "foo", PAGES);
+
+    let mut wrong = PAGES.to_vec();
+    wrong.retain(|&page| page != "inner/fn.baz.html");
+    wrong.push("struct.Missing.html");
+    let mismatch = panic::catch_unwind(|| assert_generated_files(tmp_dir(), "foo", &wrong));
+    let message = *mismatch.unwrap_err().downcast::<String>().unwrap();
+    let (extra, missing) = message.split_once("missing:").unwrap();
+    assert!(extra.contains("inner/fn.baz.html") && !extra.contains("struct.Missing.html"));
+    assert!(missing.contains("struct.Missing.html") && !missing.contains("fn.baz.html"));
+}
@@ -0,0 +1,31 @@
+// Check that the `--html-in-header`, `--html-before-content` and `--html-after-content` helpers
+// inject the given files into the generated pages, and that a missing file is reported early.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::fs;
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&out_dir)
+        .html_in_header("header.html")
+        .html_before_content("before.html")
+        .html_after_content("after.html")
+        .run();
+
+    let page = fs::read_to_string(out_dir.join("foo/fn.foo.html")).unwrap();
+    let header = page.find(r#"<meta name="injected-header">"#).unwrap();
+    let before = page.find(r#"<div id="injected-before">"#).unwrap();
+    let after = page.find(r#"<div id="injected-after">"#).unwrap();
+    assert!(header < page.find("<body").unwrap());
+    assert!(before < after);
+
+    let missing = panic::catch_unwind(|| {
+        rustdoc().html_in_header("missing.html");
+    });
+    assert!(missing.is_err());
+}
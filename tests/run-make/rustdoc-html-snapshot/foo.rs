@@ -0,0 +1,2 @@
+/// Some documentation.
+pub fn foo() {}
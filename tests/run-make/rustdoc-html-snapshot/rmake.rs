@@ -0,0 +1,30 @@
+// Check that `assert_html_matches_snapshot` blesses a golden file when `RUSTC_BLESS_TEST` is set,
+// ignores whitespace between tags, and prints a diff when the page changes.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_html_matches_snapshot, rustdoc, tmp_dir};
+use std::{env, fs, panic};
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+    let page = out_dir.join("foo/fn.foo.html");
+    let golden = tmp_dir().join("fn.foo.html");
+
+    env::set_var("RUSTC_BLESS_TEST", "--bless");
+    assert_html_matches_snapshot(&page, &golden);
+    env::remove_var("RUSTC_BLESS_TEST");
+    assert_eq!(fs::read_to_string(&golden).unwrap(), fs::read_to_string(&page).unwrap());
+    assert_html_matches_snapshot(&page, &golden);
+
+    let html = fs::read_to_string(&page).unwrap();
+    fs::write(&golden, html.replace("><", ">\n    <")).unwrap();
+    assert_html_matches_snapshot(&page, &golden);
+
+    fs::write(&golden, html.replace("Some documentation.", "Other documentation.")).unwrap();
+    let mismatch = panic::catch_unwind(|| assert_html_matches_snapshot(&page, &golden));
+    let message = mismatch.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("-<p>Other documentation.</p>"), "{message}");
+    assert!(message.contains("+<p>Some documentation.</p>"), "{message}");
+}
@@ -0,0 +1,32 @@
+// Check that `enable_index_page` and `index_page` each generate an index page, and that using
+// both together panics before running rustdoc, since `--index-page` already implies
+// `--enable-index-page`.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_xpath_text, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let generated = tmp_dir().join("generated");
+    rustdoc().input("foo.rs").out_dir(&generated).enable_index_page().run();
+    assert_xpath_text(generated.join("index.html"), "//h1", "List of all crates");
+    assert_xpath_text(generated.join("index.html"), r#"//a[@href="foo/index.html"]"#, "foo");
+
+    let custom = tmp_dir().join("custom");
+    rustdoc().input("foo.rs").out_dir(&custom).index_page("index.md").run();
+    assert_xpath_text(custom.join("index.html"), "//h1", "Custom index");
+
+    let both = panic::catch_unwind(|| {
+        rustdoc().enable_index_page().index_page("index.md");
+    });
+    assert!(both.is_err());
+    let both = panic::catch_unwind(|| {
+        rustdoc().index_page("index.md").enable_index_page();
+    });
+    assert!(both.is_err());
+    let missing = panic::catch_unwind(|| {
+        rustdoc().index_page("missing.md");
+    });
+    assert!(missing.is_err());
+}
@@ -0,0 +1,32 @@
+// Check that `input_str` documents an inline snippet as the given crate, and that the source file
+// it writes is removed once the invocation and its clones are dropped.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::fs;
+
+fn temp_inputs() -> usize {
+    fs::read_dir(tmp_dir())
+        .unwrap()
+        .filter(|entry| {
+            entry.as_ref().unwrap().file_name().to_string_lossy().starts_with("rustdoc-input-")
+        })
+        .count()
+}
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.input_str("snippet", "/// Adds one.\npub fn add_one(x: u32) -> u32 { x + 1 }\n");
+    let mut clone = doc.clone();
+    doc.out_dir(tmp_dir().join("doc")).run();
+    assert!(tmp_dir().join("doc/snippet/fn.add_one.html").exists());
+    assert_eq!(temp_inputs(), 1);
+
+    drop(doc);
+    assert_eq!(temp_inputs(), 1);
+    clone.out_dir(tmp_dir().join("clone")).run();
+    assert!(tmp_dir().join("clone/snippet/fn.add_one.html").exists());
+    drop(clone);
+    assert_eq!(temp_inputs(), 0);
+}
@@ -0,0 +1,14 @@
+/// The widget   does
+/// *useful* things.
+///
+/// # Examples
+///
+/// It is made with `Widget`.
+pub struct Widget;
+
+impl Widget {
+    /// Only the method mentions gadgets.
+    pub fn method(&self) {}
+}
+
+pub struct Undocumented;
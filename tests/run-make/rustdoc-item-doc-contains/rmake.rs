@@ -0,0 +1,31 @@
+// Check that `assert_item_doc_contains` finds text in the rendered documentation of an item,
+// across lines and markup, and rejects text which is missing or only in the docs of a method.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_item_doc_contains, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+
+    assert_item_doc_contains(
+        &out_dir,
+        "foo",
+        "struct.Widget.html",
+        "The widget does useful things.",
+    );
+    assert_item_doc_contains(&out_dir, "foo", "struct.Widget.html", "It is made with Widget.");
+
+    for (item, needle) in [
+        ("struct.Widget.html", "useless things"),
+        ("struct.Widget.html", "gadgets"),
+        ("struct.Undocumented.html", "anything"),
+        ("struct.Missing.html", "anything"),
+    ] {
+        let result =
+            panic::catch_unwind(|| assert_item_doc_contains(&out_dir, "foo", item, needle));
+        assert!(result.is_err(), "`{needle}` was found in `{item}`");
+    }
+}
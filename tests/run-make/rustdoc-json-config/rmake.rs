@@ -0,0 +1,43 @@
+// Check that `json_config` passes a single `--json` along with `--error-format=json`, and that
+// the collected diagnostics carry their rendering, colored or short when requested.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, ColorChoice, JsonOpt};
+use std::panic;
+
+fn main() {
+    rustdoc()
+        .json_config(&[JsonOpt::DiagnosticShort, JsonOpt::Artifacts, JsonOpt::DiagnosticShort])
+        .inspect(|cmd| {
+            let args: Vec<_> = cmd.get_args().skip(1).collect();
+            assert_eq!(args, ["--error-format=json", "--json=diagnostic-short,artifacts"]);
+        });
+
+    let plain = rustdoc().input("warning.rs").out_dir(tmp_dir()).run_and_collect_diagnostics();
+    let rendered = plain[0].rendered.as_deref().unwrap();
+    assert!(rendered.contains("unresolved link to `Missing`"));
+    assert!(rendered.contains("-->"));
+    assert!(!rendered.contains('\x1b'));
+
+    let ansi = rustdoc()
+        .input("warning.rs")
+        .out_dir(tmp_dir())
+        .json_config(&[JsonOpt::DiagnosticRenderedAnsi])
+        .run_and_collect_diagnostics();
+    assert!(ansi[0].rendered.as_deref().unwrap().contains("\x1b["));
+
+    let short = rustdoc()
+        .input("warning.rs")
+        .out_dir(tmp_dir())
+        .json_config(&[JsonOpt::DiagnosticShort])
+        .run_and_collect_diagnostics();
+    let rendered = short[0].rendered.as_deref().unwrap();
+    assert!(rendered.starts_with("warning.rs:"), "{rendered}");
+    assert!(!rendered.contains("-->"));
+
+    let conflict = panic::catch_unwind(|| {
+        rustdoc().color(ColorChoice::Always).json_config(&[JsonOpt::DiagnosticShort]);
+    });
+    assert!(conflict.is_err());
+}
@@ -0,0 +1,28 @@
+// Check that diagnostics emitted by rustdoc with `--error-format=json` are collected with their
+// level, code and spans.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let diagnostics =
+        rustdoc().input("warning.rs").out_dir(tmp_dir()).run_and_collect_diagnostics();
+    let warning = diagnostics
+        .iter()
+        .find(|diag| diag.level == "warning")
+        .expect("expected a broken intra-doc link warning");
+    assert_eq!(warning.code.as_deref(), Some("rustdoc::broken_intra_doc_links"));
+    assert!(warning.message.contains("DoesNotExist"));
+    assert_eq!(warning.spans[0].line_start, 1);
+
+    let diagnostics = rustdoc().input("error.rs").out_dir(tmp_dir()).run_and_collect_diagnostics();
+    let error = diagnostics
+        .iter()
+        .find(|diag| diag.level == "error")
+        .expect("expected a duplicate definition error");
+    assert_eq!(error.code.as_deref(), Some("E0428"));
+    let span = error.spans.iter().find(|span| span.is_primary).unwrap();
+    assert!(span.file_name.ends_with("error.rs"));
+    assert_eq!(span.line_start, 2);
+}
@@ -0,0 +1,2 @@
+/// Links to [`DoesNotExist`].
+pub fn broken_link() {}
@@ -0,0 +1,18 @@
+// Check the explicit JSON format version guard, on the current version and on a crafted JSON file
+// with a stale one.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc_json::assert_format_version;
+use run_make_support::rustdoc_json_types::FORMAT_VERSION;
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).assert_json_format_version(FORMAT_VERSION);
+
+    assert_format_version("old-version.json", 1);
+    let mismatch =
+        panic::catch_unwind(|| assert_format_version("old-version.json", FORMAT_VERSION));
+    assert!(mismatch.is_err());
+}
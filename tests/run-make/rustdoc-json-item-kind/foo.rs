@@ -0,0 +1,19 @@
+#![feature(extern_types)]
+pub mod inner {
+    pub struct Bar;
+
+    pub enum Choice {
+        Yes,
+        No,
+    }
+}
+
+pub use inner::Bar as Reexported;
+
+pub type Alias = inner::Bar;
+
+pub fn function() {}
+
+extern "C" {
+    pub type Foreign;
+}
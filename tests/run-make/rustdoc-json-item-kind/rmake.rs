@@ -0,0 +1,26 @@
+// Check that `assert_item_kind` checks the kind of the item at a path: a re-export has the kind of
+// the item it re-exports, a type alias is a `type_alias`, and a wrong kind panics.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let json = rustdoc().input("foo.rs").out_dir(tmp_dir()).run_json();
+    json.assert_item_kind(&["foo", "inner"], "module");
+    json.assert_item_kind(&["foo", "inner", "Bar"], "struct");
+    json.assert_item_kind(&["foo", "inner", "Choice"], "enum");
+    json.assert_item_kind(&["foo", "inner", "Choice", "Yes"], "variant");
+    json.assert_item_kind(&["foo", "Reexported"], "struct");
+    json.assert_item_kind(&["foo", "Alias"], "type_alias");
+    json.assert_item_kind(&["foo", "function"], "function");
+    json.assert_item_kind(&["foo", "Foreign"], "foreign_type");
+
+    let wrong = panic::catch_unwind(|| json.assert_item_kind(&["foo", "inner", "Bar"], "enum"));
+    let message = *wrong.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("`foo::inner::Bar` is a `struct`, but a `enum` was expected"));
+
+    let missing = panic::catch_unwind(|| json.assert_item_kind(&["foo", "Missing"], "struct"));
+    assert!(missing.is_err());
+}
@@ -0,0 +1,15 @@
+//! The crate root.
+
+/// A struct.
+pub struct Bar;
+
+impl Bar {
+    /// A method.
+    pub fn baz(&self) {}
+}
+
+mod inner {
+    pub struct Reexported;
+}
+
+pub use inner::Reexported;
@@ -0,0 +1,34 @@
+// Check that `run_json` finds and parses the JSON documentation of a crate, and that items can
+// be looked up by path.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc_json_types::{ItemEnum, FORMAT_VERSION};
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let json = rustdoc().input("foo.rs").out_dir(tmp_dir()).run_json();
+    assert_eq!(json.format_version(), FORMAT_VERSION);
+
+    let root = json.root_item();
+    assert_eq!(&root.id, json.root());
+    assert_eq!(root.name.as_deref(), Some("foo"));
+    assert_eq!(root.docs.as_deref(), Some("The crate root."));
+    assert!(matches!(root.inner, ItemEnum::Module(_)));
+
+    let bar = json
+        .index()
+        .values()
+        .find(|item| item.name.as_deref() == Some("Bar"))
+        .expect("`Bar` should be in the index");
+    assert!(matches!(bar.inner, ItemEnum::Struct(_)));
+
+    assert_eq!(json.item_by_path(&["foo", "Bar"]), Some(bar));
+    let baz = json.item_by_path(&["foo", "Bar", "baz"]).expect("`Bar::baz` should resolve");
+    assert!(matches!(baz.inner, ItemEnum::Function(_)));
+    assert_eq!(baz.docs.as_deref(), Some("A method."));
+    let reexported = json.item_by_path(&["foo", "Reexported"]).expect("re-export should resolve");
+    assert!(matches!(reexported.inner, ItemEnum::Struct(_)));
+    assert!(json.item_by_path(&["foo", "Bar", "missing"]).is_none());
+    assert!(json.item_by_path(&["foo", "Missing"]).is_none());
+}
@@ -0,0 +1,26 @@
+// Check that `passes` and `no_defaults` pass the legacy flags without `-Z unstable-options`, and
+// that rustdoc only warns that they no longer do anything.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.passes("strip-hidden").passes("strip-private").no_defaults();
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = ["--passes", "strip-hidden", "--passes", "strip-private", "--no-defaults"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+    assert!(!args.contains(&OsStr::new("unstable-options")));
+
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(tmp_dir())
+        .passes("strip-hidden")
+        .no_defaults()
+        .run_and_capture()
+        .assert_exit_code(0)
+        .assert_stderr_contains("the `passes` flag no longer functions")
+        .assert_stderr_contains("the `no-defaults` flag no longer functions");
+}
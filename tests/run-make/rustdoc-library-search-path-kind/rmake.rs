@@ -0,0 +1,22 @@
+// Check that `library_search_path_kind` passes `-L {kind}={path}` for every `LibraryKind`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, LibraryKind};
+use std::ffi::OsStr;
+
+fn main() {
+    let kinds = [
+        (LibraryKind::Dependency, "dependency=libs"),
+        (LibraryKind::Crate, "crate=libs"),
+        (LibraryKind::Native, "native=libs"),
+        (LibraryKind::Framework, "framework=libs"),
+        (LibraryKind::All, "all=libs"),
+    ];
+    for (kind, expected) in kinds {
+        let mut doc = rustdoc();
+        doc.library_search_path_kind(kind, "libs");
+        let args: Vec<_> = doc.command_mut().get_args().collect();
+        assert!(args.ends_with(&["-L", expected].map(OsStr::new)), "{kind:?}: {args:?}");
+    }
+}
@@ -0,0 +1,5 @@
+pub struct Bar;
+
+pub fn make() -> Bar {
+    Bar
+}
@@ -0,0 +1,20 @@
+// Check that `generate_link_to_definition` links the identifiers of the source pages, which
+// `assert_src_links` detects, and that the source pages have no such link without it.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_src_links, assert_xpath_text, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let linked = tmp_dir().join("linked");
+    rustdoc().input("foo.rs").out_dir(&linked).generate_link_to_definition().run();
+    let src = linked.join("src/foo/foo.rs.html");
+    assert_src_links(&src);
+    assert_xpath_text(&src, r#"//pre[@class="rust"]//a[@href="../../foo/struct.Bar.html"]"#, "Bar");
+
+    let plain = tmp_dir().join("plain");
+    rustdoc().input("foo.rs").out_dir(&plain).run();
+    let unlinked = panic::catch_unwind(|| assert_src_links(plain.join("src/foo/foo.rs.html")));
+    assert!(unlinked.is_err());
+}
@@ -0,0 +1,2 @@
+/// See [the removed page](gone.html#section) and the [existing one](struct.Foo.html).
+pub struct Foo;
@@ -0,0 +1,15 @@
+//! Links to [`inner::Bar`], [`Trait`] and [the book](https://doc.rust-lang.org/book/#intro).
+
+pub mod inner {
+    /// Implements [`crate::Trait`], see [`Self::method`].
+    pub struct Bar;
+
+    impl Bar {
+        pub fn method(&self) {}
+    }
+
+    impl crate::Trait for Bar {}
+}
+
+/// A trait implemented by [`inner::Bar`].
+pub trait Trait {}
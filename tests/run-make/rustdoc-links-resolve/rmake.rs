@@ -0,0 +1,21 @@
+// Check that `assert_links_resolve` accepts the docs of a crate with cross-item links, and lists
+// a dangling link of another one. Both get an index page, which `help.html` and `settings.html`
+// link to.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_links_resolve, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let linked = tmp_dir().join("linked");
+    rustdoc().input("linked.rs").out_dir(&linked).enable_index_page().run();
+    assert_links_resolve(&linked);
+
+    let dangling = tmp_dir().join("dangling");
+    rustdoc().input("dangling.rs").out_dir(&dangling).enable_index_page().run();
+    let broken = panic::catch_unwind(|| assert_links_resolve(&dangling));
+    let message = *broken.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("dangling/struct.Foo.html: gone.html#section"), "{message}");
+    assert!(!message.contains("struct.Foo.html: struct.Foo.html"), "{message}");
+}
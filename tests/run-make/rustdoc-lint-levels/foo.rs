@@ -0,0 +1,3 @@
+//! Crate docs.
+
+pub fn undocumented() {}
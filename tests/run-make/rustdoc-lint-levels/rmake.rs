@@ -0,0 +1,45 @@
+// Check the flags passed by `lint` and its `allow`/`warn`/`deny`/`forbid` shorthands, that they
+// keep their order so that the last level given wins, and that `cap_lints` caps them.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, LintLevel};
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.allow("dead_code")
+        .warn("missing_docs")
+        .deny("unused")
+        .forbid("unsafe_code")
+        .lint(LintLevel::Warn, "unused")
+        .cap_lints(LintLevel::Warn);
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = [
+        "-A",
+        "dead_code",
+        "-W",
+        "missing_docs",
+        "-D",
+        "unused",
+        "-F",
+        "unsafe_code",
+        "-W",
+        "unused",
+        "--cap-lints",
+        "warn",
+    ];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).deny("missing_docs").run_fail();
+    rustdoc().input("foo.rs").out_dir(&out_dir).deny("missing_docs").allow("missing_docs").run();
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&out_dir)
+        .deny("missing_docs")
+        .cap_lints(LintLevel::Warn)
+        .run_and_capture()
+        .assert_exit_code(0)
+        .assert_stderr_contains("missing documentation for a function");
+}
@@ -0,0 +1,33 @@
+// Check that `markdown` renders a standalone markdown file, with `markdown_no_toc` and
+// `markdown_css` applied, and that it rejects files which aren't markdown.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_has_xpath, assert_no_xpath, assert_xpath_text, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("toc");
+    rustdoc().markdown("doc.md").out_dir(&out_dir).run();
+    let page = out_dir.join("doc.html");
+    assert_xpath_text(&page, "//h1", "Standalone page");
+    assert_xpath_text(&page, "//h2", "First section");
+    assert_has_xpath(&page, "//nav[@id='TOC']");
+
+    let out_dir = tmp_dir().join("no-toc");
+    rustdoc()
+        .markdown("doc.md")
+        .out_dir(&out_dir)
+        .markdown_no_toc()
+        .markdown_css("style.css")
+        .run();
+    let page = out_dir.join("doc.html");
+    assert_xpath_text(&page, "//h1", "Standalone page");
+    assert_no_xpath(&page, "//nav[@id='TOC']");
+    assert_has_xpath(&page, "//link[@rel='stylesheet' and @href='style.css']");
+
+    let not_markdown = panic::catch_unwind(|| {
+        rustdoc().markdown("doc.txt");
+    });
+    assert!(not_markdown.is_err());
+}
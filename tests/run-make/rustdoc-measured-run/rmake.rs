@@ -0,0 +1,19 @@
+// Check that `measured_run` returns the output of rustdoc along with a plausible duration for
+// documenting a trivial crate.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::time::Duration;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let (process, elapsed) = rustdoc().input("foo.rs").out_dir(&out_dir).measured_run();
+    process.assert_exit_code(0);
+    assert!(out_dir.join("foo/struct.Foo.html").exists());
+    assert!(elapsed > Duration::ZERO);
+    assert!(elapsed < Duration::from_secs(120), "documenting took {elapsed:?}");
+
+    let (version, _) = rustdoc().arg("--version").measured_run();
+    version.assert_stdout_contains("rustdoc");
+}
@@ -0,0 +1,27 @@
+// Check that constructing an invocation without the environment variables compiletest sets
+// panics with the list of the missing ones, instead of failing later to find a program.
+
+extern crate run_make_support;
+
+use run_make_support::{bare_rustdoc, rustdoc};
+use std::{env, panic};
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    *panic::catch_unwind(f).unwrap_err().downcast::<String>().unwrap()
+}
+
+fn main() {
+    let rustdoc_path = env::var_os("RUSTDOC").unwrap();
+    let target_rpath_dir = env::var_os("TARGET_RPATH_DIR").unwrap();
+    env::remove_var("RUSTDOC");
+    env::set_var("TARGET_RPATH_DIR", "");
+
+    let message = panic_message(|| drop(rustdoc()));
+    assert!(message.contains(r#"["RUSTDOC", "TARGET_RPATH_DIR"]"#), "{message}");
+    let message = panic_message(|| drop(bare_rustdoc()));
+    assert!(message.contains(r#"["RUSTDOC"]"#), "{message}");
+
+    env::set_var("RUSTDOC", rustdoc_path);
+    env::set_var("TARGET_RPATH_DIR", target_rpath_dir);
+    rustdoc().arg("--version").run();
+}
@@ -0,0 +1,4 @@
+//! A clean crate.
+
+/// Links to [`Foo`].
+pub struct Foo;
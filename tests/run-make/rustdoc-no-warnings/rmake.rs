@@ -0,0 +1,31 @@
+// Check that `assert_no_warnings` passes on a crate documented without warnings and fails on one
+// with two, which `warning_count` counts without the summary, and that both require JSON
+// diagnostics.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, ErrorFormat};
+use std::panic;
+
+fn main() {
+    let clean = rustdoc()
+        .input("clean.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Json)
+        .warn("missing_docs")
+        .run_and_capture();
+    clean.assert_no_warnings();
+    assert_eq!(clean.warning_count(), 0);
+
+    let warnings = rustdoc()
+        .input("two-warnings.rs")
+        .out_dir(tmp_dir())
+        .error_format(ErrorFormat::Json)
+        .warn("missing_docs")
+        .run_and_capture();
+    assert_eq!(warnings.warning_count(), 2);
+    assert!(panic::catch_unwind(|| warnings.assert_no_warnings()).is_err());
+
+    let human = rustdoc().input("two-warnings.rs").out_dir(tmp_dir()).run_and_capture();
+    assert!(panic::catch_unwind(|| human.warning_count()).is_err());
+}
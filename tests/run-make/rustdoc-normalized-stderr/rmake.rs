@@ -0,0 +1,23 @@
+// Check that `normalized_stderr` replaces the sysroot by `$SYSROOT` and applies the given
+// replacements after it, here on the error about an input missing from the sysroot.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, rustdoc, tmp_dir};
+
+fn main() {
+    let output = rustc().arg("--print").arg("sysroot").run();
+    let sysroot = String::from_utf8(output.stdout).unwrap().trim_end().to_owned();
+    let process = rustdoc()
+        .input(format!("{sysroot}/missing.rs"))
+        .out_dir(tmp_dir())
+        .run_fail_with_code(1);
+    assert!(process.stderr_utf8().contains(&sysroot));
+
+    let stderr = process.normalized_stderr(&[]);
+    assert!(stderr.contains("$SYSROOT/missing.rs"), "{stderr}");
+    assert!(!stderr.contains(&sysroot));
+
+    let stderr = process.normalized_stderr(&[("$SYSROOT/missing", "$INPUT"), ("rs", "RS")]);
+    assert!(stderr.contains("$INPUT.RS"), "{stderr}");
+}
@@ -0,0 +1,28 @@
+// Check that `out_dir` and `legacy_output` each write the docs where they are told to, and that
+// setting both panics instead of failing in rustdoc.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    rustdoc().input("foo.rs").out_dir(tmp_dir().join("out-dir")).run();
+    assert!(tmp_dir().join("out-dir/foo/struct.Foo.html").exists());
+
+    rustdoc().input("foo.rs").legacy_output(tmp_dir().join("output")).run();
+    assert!(tmp_dir().join("output/foo/struct.Foo.html").exists());
+
+    let out_dir_first = panic::catch_unwind(|| {
+        rustdoc().out_dir(tmp_dir()).legacy_output(tmp_dir());
+    });
+    assert!(out_dir_first.is_err());
+    let output_first = panic::catch_unwind(|| {
+        rustdoc().legacy_output(tmp_dir()).out_dir(tmp_dir());
+    });
+    assert!(output_first.is_err());
+    let short_flag = panic::catch_unwind(|| {
+        rustdoc().arg("-o").arg(tmp_dir()).legacy_output(tmp_dir());
+    });
+    assert!(short_flag.is_err());
+}
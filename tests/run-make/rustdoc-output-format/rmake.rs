@@ -0,0 +1,36 @@
+// Check that `output_format_typed` renders `--output-format html|json`, adding
+// `-Z unstable-options` for JSON only, and that `output_format` rejects unknown formats.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, OutputFormat};
+use std::ffi::OsStr;
+use std::panic;
+
+fn main() {
+    let mut html = rustdoc();
+    html.output_format_typed(OutputFormat::Html);
+    let args: Vec<_> = html.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--output-format", "html"].map(OsStr::new)));
+    assert!(!args.contains(&OsStr::new("unstable-options")));
+
+    let mut json = rustdoc();
+    json.output_format_typed(OutputFormat::Json);
+    let args: Vec<_> = json.command_mut().get_args().collect();
+    let expected = ["-Z", "unstable-options", "--output-format", "json"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let mut by_name = rustdoc();
+    by_name.output_format("json");
+    let args: Vec<_> = by_name.command_mut().get_args().collect();
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let out_dir = tmp_dir().join("json");
+    rustdoc().input("foo.rs").out_dir(&out_dir).output_format_typed(OutputFormat::Json).run();
+    assert!(out_dir.join("foo.json").exists());
+
+    let typo = panic::catch_unwind(|| {
+        rustdoc().output_format("jsn");
+    });
+    assert!(typo.is_err());
+}
@@ -0,0 +1,11 @@
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+///
+/// ```
+/// assert!(true);
+/// ```
+pub fn documented() {}
+
+/// Links to [`Missing`].
+pub struct Broken;
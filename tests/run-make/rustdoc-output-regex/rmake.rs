@@ -0,0 +1,25 @@
+// Check the regex assertions of `CompletedProcess` against output with variable content, like
+// the line numbers of the doctests and the time they took, and extracting capture groups.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let tests = rustdoc().input("doctests.rs").arg("--test").run_and_capture();
+    tests
+        .assert_exit_code(0)
+        .assert_stdout_matches_regex(r"test doctests\.rs - documented \(line \d+\) \.\.\. ok")
+        .assert_stdout_matches_regex(r"finished in \d+\.\d+s");
+    let summary = tests.stdout_captures(r"test result: ok\. (\d+) passed; (\d+) failed(; x)?");
+    assert_eq!(summary, Some(vec!["2".to_string(), "0".to_string(), String::new()]));
+    assert_eq!(tests.stdout_captures(r"(\d+) exploded"), None);
+
+    let docs = rustdoc().input("doctests.rs").out_dir(tmp_dir()).run_and_capture();
+    docs.assert_stderr_matches_regex(r"--> doctests\.rs:\d+:\d+");
+
+    assert!(panic::catch_unwind(|| tests.assert_stdout_matches_regex(r"\d+ exploded")).is_err());
+    assert!(panic::catch_unwind(|| docs.assert_stderr_matches_regex(r"error\[E\d+\]")).is_err());
+    assert!(panic::catch_unwind(|| tests.assert_stdout_matches_regex(r"(unclosed")).is_err());
+}
@@ -0,0 +1,28 @@
+// Check that `enable_per_target_ignores` makes rustdoc ignore the examples marked with
+// `ignore-{target}` for the current target, while the ones for other targets still run.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, target};
+
+fn main() {
+    let source = format!(
+        r#"/// ```ignore-{target}
+/// panic!("must not run on this target");
+/// ```
+///
+/// ```ignore-not-a-real-target
+/// assert_eq!(1 + 1, 2);
+/// ```
+pub fn foo() {{}}
+"#,
+        target = target()
+    );
+
+    let summary = rustdoc().input_str("foo", &source).enable_per_target_ignores().run_doctests();
+    assert_eq!((summary.passed, summary.ignored, summary.failed), (1, 1, 0));
+
+    // Without the flag, `ignore-{target}` is an unknown attribute and the example runs.
+    let summary = rustdoc().input_str("foo", &source).run_doctests();
+    assert_eq!((summary.passed, summary.ignored, summary.failed), (1, 0, 1));
+}
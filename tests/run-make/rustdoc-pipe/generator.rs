@@ -0,0 +1,6 @@
+fn main() {
+    for i in 0..3 {
+        println!("/// Generated item number {i}.");
+        println!("pub struct Generated{i};");
+    }
+}
@@ -0,0 +1,20 @@
+// Check that `pipe` feeds the output of a generator to rustdoc as it runs.
+
+extern crate run_make_support;
+
+use run_make_support::{bin_name, pipe, rustc, rustdoc, tmp_dir};
+use std::process::Command;
+
+fn main() {
+    rustc().input("generator.rs").run();
+    let mut generator = Command::new(tmp_dir().join(bin_name("generator")));
+
+    let out_dir = tmp_dir().join("doc");
+    let mut doc = rustdoc();
+    doc.input("-").crate_name("generated").out_dir(&out_dir);
+    let process = pipe(&mut generator, &mut doc);
+    process.assert_exit_code(0);
+    for i in 0..3 {
+        assert!(out_dir.join(format!("generated/struct.Generated{i}.html")).exists());
+    }
+}
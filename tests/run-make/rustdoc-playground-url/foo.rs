@@ -0,0 +1,4 @@
+/// ```
+/// let x = 1;
+/// ```
+pub fn foo() {}
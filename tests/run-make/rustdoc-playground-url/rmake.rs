@@ -0,0 +1,40 @@
+// Check that `playground_url` and `markdown_playground_url` pass their own flag, that the
+// examples get a "Run" button, and that URLs which aren't absolute `http(s)` ones are rejected.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_has_xpath, rustdoc, tmp_dir};
+use std::ffi::OsStr;
+use std::panic;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.playground_url("https://play.example.org/");
+    doc.markdown_playground_url("http://localhost:8000/md");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = [
+        "--playground-url",
+        "https://play.example.org/",
+        "--markdown-playground-url",
+        "http://localhost:8000/md",
+    ];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).playground_url("https://play.example.org/").run();
+    assert_has_xpath(
+        out_dir.join("foo/fn.foo.html"),
+        "//a[@class='test-arrow' and starts-with(@href, 'https://play.example.org/')]",
+    );
+
+    for url in ["play.example.org", "/playground", "ftp://play.example.org/", "https://"] {
+        let playground = panic::catch_unwind(|| {
+            rustdoc().playground_url(url);
+        });
+        assert!(playground.is_err(), "`{url}` was accepted");
+        let markdown = panic::catch_unwind(|| {
+            rustdoc().markdown_playground_url(url);
+        });
+        assert!(markdown.is_err(), "`{url}` was accepted");
+    }
+}
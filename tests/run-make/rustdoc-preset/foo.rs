@@ -0,0 +1,4 @@
+pub use alpha::Alpha;
+pub use beta::Beta;
+
+pub async fn wait() {}
@@ -0,0 +1,42 @@
+// Check that invocations derived from a `RustdocPreset` start with its edition and externs, and
+// that customizing one of them affects neither the preset nor the other invocation.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir, Rustdoc, RustdocPreset};
+
+fn main() {
+    rustc().input("alpha.rs").crate_type("lib").run();
+    rustc().input("beta.rs").crate_type("lib").run();
+
+    let mut preset = RustdocPreset::new();
+    preset
+        .input("foo.rs")
+        .edition("2018")
+        .extern_("alpha", tmp_dir().join("libalpha.rlib"))
+        .extern_("beta", tmp_dir().join("libbeta.rlib"));
+
+    let mut first = Rustdoc::from_preset(&preset);
+    first.crate_name("first").out_dir(tmp_dir().join("first"));
+    let mut second = Rustdoc::from_preset(&preset);
+    second.crate_name("second").out_dir(tmp_dir().join("second")).document_private_items();
+    first.run();
+    second.run();
+
+    assert!(tmp_dir().join("first/first/struct.Alpha.html").exists());
+    assert!(tmp_dir().join("second/second/struct.Beta.html").exists());
+    assert!(!tmp_dir().join("first/second").exists());
+
+    first.inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().collect();
+        assert!(args.contains(&"first".as_ref()));
+        assert!(!args.contains(&"second".as_ref()));
+        assert!(!args.contains(&"--document-private-items".as_ref()));
+    });
+    preset.inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args.iter().filter(|&&arg| arg == "--extern").count(), 2);
+        assert!(!args.contains(&"--crate-name".as_ref()));
+        assert!(!args.contains(&"--out-dir".as_ref()));
+    });
+}
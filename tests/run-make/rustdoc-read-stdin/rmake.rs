@@ -0,0 +1,31 @@
+// Check that rustdoc reads the crate from the standard input when its input is `-`, both to
+// document it and to run its doctests, and that it rejects a crate that isn't valid UTF-8.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+const INPUT: &str = r#"
+//! ```
+//! assert_eq!(1 + 1, 2);
+//! ```
+
+/// A struct read from the standard input.
+pub struct F;
+"#;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().arg("-").out_dir(&out_dir).stdin(INPUT).run();
+    assert!(out_dir.join("rust_out/struct.F.html").exists());
+
+    let output = rustdoc().arg("--test").arg("-").stdin(INPUT).run();
+    assert!(String::from_utf8(output.stdout).unwrap().contains("test result: ok. 1 passed"));
+
+    rustdoc()
+        .arg("-")
+        .out_dir(&out_dir)
+        .stdin(b"\xff")
+        .run_fail_with_code(1)
+        .assert_stderr_contains("couldn't read from stdin, as it did not contain valid UTF-8");
+}
@@ -0,0 +1,13 @@
+pub use hidden::Bar;
+pub use private::Quz;
+
+mod private {
+    pub struct Quz;
+}
+
+#[doc(hidden)]
+pub mod hidden {
+    pub struct Bar;
+}
+
+pub struct Here;
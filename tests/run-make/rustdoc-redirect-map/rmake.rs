@@ -0,0 +1,35 @@
+// Check that `generate_redirect_map` writes the redirect map instead of the redirect pages, and
+// that `read_redirect_map` finds the pages the re-exported items moved to. Also check that
+// `RedirectMap::assert_redirect` and `assert_no_redirect` reject the wrong mappings.
+
+extern crate run_make_support;
+
+use run_make_support::{read_redirect_map, rustdoc, tmp_dir, RedirectMap};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).generate_redirect_map().run();
+
+    let map = read_redirect_map(&out_dir);
+    assert_eq!(map["foo/private/struct.Quz.html"], "foo/struct.Quz.html");
+    assert_eq!(map["foo/hidden/struct.Bar.html"], "foo/struct.Bar.html");
+    assert!(!out_dir.join("foo/private/struct.Quz.html").exists());
+
+    let map = RedirectMap::read(&out_dir);
+    assert_eq!(map.target_of("foo/private/struct.Quz.html"), Some("foo/struct.Quz.html"));
+    assert_eq!(map.target_of("foo/struct.Here.html"), None);
+
+    map.assert_redirect("foo/hidden/struct.Bar.html", "foo/struct.Bar.html");
+    map.assert_no_redirect("foo/struct.Here.html");
+    let wrong_target = panic::catch_unwind(|| {
+        map.assert_redirect("foo/hidden/struct.Bar.html", "foo/struct.Quz.html");
+    });
+    assert!(wrong_target.is_err());
+    let absent = panic::catch_unwind(|| {
+        map.assert_redirect("foo/struct.Here.html", "foo/struct.Bar.html");
+    });
+    assert!(absent.is_err());
+    let present = panic::catch_unwind(|| map.assert_no_redirect("foo/private/struct.Quz.html"));
+    assert!(present.is_err());
+}
@@ -0,0 +1,30 @@
+// Check that `remap_path_prefix` passes one `--remap-path-prefix {from}={to}` per mapping, and
+// that it rejects paths containing `=` on either side. This rustdoc does not accept the flag yet,
+// so only the arguments are checked.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::ffi::OsStr;
+use std::panic;
+
+fn main() {
+    let mut single = rustdoc();
+    single.remap_path_prefix("/home/user/project", "/remapped");
+    let args: Vec<_> = single.command_mut().get_args().collect();
+    let expected = ["--remap-path-prefix", "/home/user/project=/remapped"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let mut several = rustdoc();
+    several.remap_path_prefix("/first", "/a").remap_path_prefix("/second", "/b");
+    let args: Vec<_> = several.command_mut().get_args().collect();
+    let expected = ["--remap-path-prefix", "/first=/a", "--remap-path-prefix", "/second=/b"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    for (from, to) in [("/with=equals", "/remapped"), ("/project", "/with=equals")] {
+        let rejected = panic::catch_unwind(|| {
+            rustdoc().remap_path_prefix(from, to);
+        });
+        assert!(rejected.is_err(), "`{from}` -> `{to}` was accepted");
+    }
+}
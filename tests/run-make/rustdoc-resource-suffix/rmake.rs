@@ -0,0 +1,27 @@
+// Check that `resource_suffix` renames the files specific to the invocation, and that
+// `resolved_asset` finds them as well as the hashed static files like the main CSS file.
+
+extern crate run_make_support;
+
+use run_make_support::{resolved_asset, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).resource_suffix("-suffix").run();
+
+    let search_index = resolved_asset(&out_dir, "search-index.js", "-suffix");
+    assert_eq!(search_index, out_dir.join("search-index-suffix.js"));
+    assert!(search_index.exists());
+    let sidebar = resolved_asset(&out_dir, "foo/sidebar-items.js", "-suffix");
+    assert_eq!(sidebar, out_dir.join("foo/sidebar-items-suffix.js"));
+
+    let css = resolved_asset(&out_dir, "rustdoc.css", "-suffix");
+    assert!(css.exists());
+    assert_eq!(css.parent().unwrap(), out_dir.join("static.files"));
+    let name = css.file_name().unwrap().to_str().unwrap();
+    assert!(name.starts_with("rustdoc-") && name.ends_with(".css"), "{name}");
+
+    let unsuffixed = panic::catch_unwind(|| resolved_asset(&out_dir, "search-index.js", ""));
+    assert!(unsuffixed.is_err());
+}
@@ -0,0 +1,29 @@
+// Check that `run_and_capture` returns the output of rustdoc whatever its exit status, and that
+// the `CompletedProcess` assertions panic when they don't hold.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let warning = rustdoc().input("warning.rs").out_dir(tmp_dir()).run_and_capture();
+    assert!(warning.status().success());
+    assert!(warning.stdout_utf8().is_empty());
+    assert!(warning.stderr_utf8().contains("unresolved link to `Missing`"));
+    warning
+        .assert_exit_code(0)
+        .assert_stderr_contains("warning")
+        .assert_stderr_contains("rustdoc::broken_intra_doc_links");
+
+    let error = rustdoc().input("error.rs").out_dir(tmp_dir()).run_and_capture();
+    assert!(!error.status().success());
+    error.assert_exit_code(1).assert_stderr_contains("E0428");
+
+    let version = rustdoc().arg("--version").run_and_capture();
+    version.assert_exit_code(0).assert_stdout_contains("rustdoc");
+
+    assert!(panic::catch_unwind(|| version.assert_stdout_contains("not a rustdoc")).is_err());
+    assert!(panic::catch_unwind(|| warning.assert_stderr_contains("E0428")).is_err());
+    assert!(panic::catch_unwind(|| error.assert_exit_code(0)).is_err());
+}
@@ -0,0 +1,20 @@
+// Check that `run_fail_with_code` accepts the exit code rustdoc reports for errors, returns the
+// captured output, and panics when the exit code is not the expected one.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    rustdoc()
+        .input("error.rs")
+        .out_dir(tmp_dir())
+        .run_fail_with_code(1)
+        .assert_stderr_contains("error[E0428]");
+
+    let wrong_code = panic::catch_unwind(|| {
+        rustdoc().input("error.rs").out_dir(tmp_dir()).run_fail_with_code(101);
+    });
+    assert!(wrong_code.is_err());
+}
@@ -0,0 +1,30 @@
+// Check that `run_in_tempdir` documents into a new directory for each run, which is removed once
+// the returned guard is dropped, and that it uses a configured output directory instead, which is
+// kept.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let (process, first) = rustdoc().input("foo.rs").run_in_tempdir();
+    process.assert_exit_code(0);
+    let page = first.path().join("foo/struct.Foo.html");
+    assert!(page.exists());
+
+    let (_, second) = rustdoc().input("foo.rs").run_in_tempdir();
+    assert_ne!(first.path(), second.path());
+    assert!(second.path().join("foo/struct.Foo.html").exists());
+
+    let first_path = first.path().to_owned();
+    drop(first);
+    assert!(!first_path.exists());
+    assert!(second.path().exists());
+
+    let configured_dir = tmp_dir().join("configured");
+    let (process, configured) = rustdoc().input("foo.rs").out_dir(&configured_dir).run_in_tempdir();
+    process.assert_exit_code(0);
+    assert_eq!(configured.path(), configured_dir);
+    drop(configured);
+    assert!(configured_dir.join("foo/struct.Foo.html").exists());
+}
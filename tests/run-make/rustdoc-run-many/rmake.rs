@@ -0,0 +1,37 @@
+// Check that `run_many` runs independent invocations and returns their outputs in order, and
+// that it rejects invocations writing to the same output directory.
+
+extern crate run_make_support;
+
+use run_make_support::{run_many, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let crates = ["first", "second", "third"];
+    let invocations = crates
+        .iter()
+        .map(|name| {
+            let mut doc = rustdoc();
+            doc.input(format!("{name}.rs")).out_dir(tmp_dir().join(name));
+            doc
+        })
+        .collect();
+    let results = run_many(invocations);
+    assert_eq!(results.len(), 3);
+    for (name, process) in crates.iter().zip(&results) {
+        process.assert_exit_code(0);
+        let mut item = name.to_string();
+        item[..1].make_ascii_uppercase();
+        assert!(tmp_dir().join(format!("{name}/{name}/struct.{item}.html")).exists());
+    }
+
+    let clobbering = panic::catch_unwind(|| {
+        let mut first = rustdoc();
+        first.input("first.rs").out_dir(tmp_dir().join("shared"));
+        let mut second = rustdoc();
+        second.input("second.rs").out_dir(tmp_dir().join("shared"));
+        run_many(vec![first, second]);
+    });
+    assert!(clobbering.is_err());
+    assert!(!tmp_dir().join("shared").exists());
+}
@@ -0,0 +1,23 @@
+// Check that `run_unchecked` returns `Ok` when rustdoc succeeds and `Err` when it fails, with the
+// captured output available in both cases.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+
+fn main() {
+    let warning = match rustdoc().input("warning.rs").out_dir(tmp_dir()).run_unchecked() {
+        Ok(process) => process,
+        Err(process) => panic!("rustdoc failed:\n{}", process.stderr_utf8()),
+    };
+    warning.assert_exit_code(0).assert_stderr_contains("unresolved link to `Missing`");
+
+    let error = match rustdoc().input("error.rs").out_dir(tmp_dir()).run_unchecked() {
+        Ok(_) => panic!("rustdoc unexpectedly succeeded"),
+        Err(process) => process,
+    };
+    error.assert_exit_code(1).assert_stderr_contains("E0428");
+
+    let version = rustdoc().arg("--version").run_unchecked().unwrap();
+    version.assert_stdout_contains("rustdoc");
+}
@@ -0,0 +1,5 @@
+/// ```
+/// assert_eq!(std::env::var("RUNTOOL_MARKER").as_deref(), Ok("set by env"));
+/// assert_eq!(std::env::var("RUNTOOL_OTHER").as_deref(), Ok("also"));
+/// ```
+pub fn run_through_env() {}
@@ -0,0 +1,30 @@
+// Check that `runtool` and `runtool_arg` pass their flags in order with a single
+// `-Z unstable-options`, and that the doctests are run through the runtool with its arguments.
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.runtool("env").runtool_arg("RUNTOOL_MARKER=set by env").runtool_arg("RUNTOOL_OTHER=also");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = [
+        "-Z",
+        "unstable-options",
+        "--runtool",
+        "env",
+        "--runtool-arg",
+        "RUNTOOL_MARKER=set by env",
+        "--runtool-arg",
+        "RUNTOOL_OTHER=also",
+    ];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let summary = doc.input("lib.rs").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+    let summary = rustdoc().input("lib.rs").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (0, 1));
+}
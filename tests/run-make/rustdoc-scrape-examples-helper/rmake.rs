@@ -0,0 +1,45 @@
+// Check the scrape-examples builder methods and the `run_scrape_examples` helper which
+// orchestrates both rustdoc passes.
+
+extern crate run_make_support;
+
+use run_make_support::{run_scrape_examples, rustc, rustdoc, tmp_dir};
+use std::fs;
+
+fn main() {
+    // Run both passes by hand first.
+    let out_dir = tmp_dir().join("manual");
+    rustc().input("src/lib.rs").arg("--crate-name=foobar").crate_type("lib").emit("metadata").run();
+    let calls = tmp_dir().join("manual.calls");
+    rustdoc()
+        .input("examples/ex.rs")
+        .crate_name("ex")
+        .crate_type("bin")
+        .out_dir(&out_dir)
+        .extern_("foobar", tmp_dir().join("libfoobar.rmeta"))
+        .scrape_examples_output_path(&calls)
+        .scrape_examples_target_crate("foobar")
+        .run();
+    assert!(calls.exists());
+    rustdoc()
+        .input("src/lib.rs")
+        .crate_name("foobar")
+        .crate_type("lib")
+        .out_dir(&out_dir)
+        .with_examples(&calls)
+        .run();
+    let page = fs::read_to_string(out_dir.join("foobar/fn.ok.html")).unwrap();
+    assert!(page.contains("scraped-example"));
+
+    // Then let the helper do the same with two examples.
+    let out_dir = tmp_dir().join("helper");
+    run_scrape_examples(
+        "foobar",
+        "src/lib.rs",
+        &[("ex", "examples/ex.rs"), ("ex2", "examples/ex2.rs")],
+        &out_dir,
+    );
+    let page = fs::read_to_string(out_dir.join("foobar/fn.ok.html")).unwrap();
+    assert!(page.contains(r#"href="../src/ex/ex.rs.html#2""#));
+    assert!(page.contains(r#"href="../src/ex2/ex2.rs.html#2""#));
+}
@@ -0,0 +1,22 @@
+pub struct Widget;
+
+impl Widget {
+    pub fn method(&self) {}
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+
+    fn describe(&self) {}
+}
+
+pub mod inner {
+    pub fn helper() {}
+}
+
+#[macro_export]
+macro_rules! make_widget {
+    () => {
+        $crate::Widget
+    };
+}
@@ -0,0 +1,30 @@
+// Check that `SearchIndex::kind_of` and `assert_indexed_as` report the kinds items are indexed
+// as, for plain and qualified names, and that `assert_indexed_as` rejects the wrong kind.
+
+extern crate run_make_support;
+
+use run_make_support::search_index::ItemKind;
+use run_make_support::{read_search_index, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+    let index = read_search_index(&out_dir);
+
+    index.assert_indexed_as("Widget", ItemKind::Struct);
+    index.assert_indexed_as("foo::Widget", ItemKind::Struct);
+    index.assert_indexed_as("Widget::method", ItemKind::Method);
+    index.assert_indexed_as("foo::Widget::method", ItemKind::Method);
+    index.assert_indexed_as("Shape::area", ItemKind::TyMethod);
+    index.assert_indexed_as("Shape::describe", ItemKind::Method);
+    index.assert_indexed_as("make_widget", ItemKind::Macro);
+    index.assert_indexed_as("inner::helper", ItemKind::Function);
+    assert_eq!(index.kind_of("foo::inner::helper"), Some(ItemKind::Function));
+    assert_eq!(index.kind_of("Shape"), Some(ItemKind::Trait));
+    assert_eq!(index.kind_of("Widget::area"), None);
+    assert_eq!(index.kind_of("missing"), None);
+
+    assert!(panic::catch_unwind(|| index.assert_indexed_as("method", ItemKind::Function)).is_err());
+    assert!(panic::catch_unwind(|| index.assert_indexed_as("missing", ItemKind::Struct)).is_err());
+}
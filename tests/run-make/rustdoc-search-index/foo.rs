@@ -0,0 +1,13 @@
+pub struct Bar {
+    pub field: u32,
+}
+
+impl Bar {
+    pub fn method(&self) {}
+}
+
+pub fn free_function() {}
+
+pub mod inner {
+    pub fn nested() {}
+}
@@ -0,0 +1,27 @@
+// Check that `read_search_index` finds and parses the search index of a documented crate.
+
+extern crate run_make_support;
+
+use run_make_support::search_index::ItemKind;
+use run_make_support::{read_search_index, rustdoc, tmp_dir};
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+
+    let index = read_search_index(&out_dir);
+    assert!(index.contains("free_function"));
+    assert!(index.contains("Bar"));
+    assert!(!index.contains("missing"));
+
+    let find = |name: &str| index.entries().find(|entry| entry.name == name).unwrap();
+    let bar = find("Bar");
+    assert_eq!((bar.crate_name.as_str(), bar.path.as_str()), ("foo", "foo"));
+    assert_eq!(bar.kind, ItemKind::Struct);
+    assert_eq!(find("free_function").kind, ItemKind::Function);
+    let method = find("method");
+    assert_eq!(method.kind, ItemKind::Method);
+    assert_eq!(method.parent.as_deref(), Some("Bar"));
+    assert_eq!(find("field").kind, ItemKind::StructField);
+    assert_eq!(find("nested").path, "foo::inner");
+}
@@ -0,0 +1,24 @@
+// Check that `spawn` lets a test write the crate to the standard input of a running rustdoc, and
+// kill an invocation which waits for its input.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::io::Write;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let mut child = rustdoc().input("-").crate_name("spawned").out_dir(&out_dir).spawn();
+    let mut stdin = child.stdin();
+    stdin.write_all(b"pub struct Written;\n").unwrap();
+    drop(stdin);
+    child.wait().assert_exit_code(0);
+    assert!(out_dir.join("spawned/struct.Written.html").exists());
+
+    let mut waiting =
+        rustdoc().input("-").crate_name("killed").out_dir(tmp_dir().join("killed")).spawn();
+    waiting.kill();
+    let process = waiting.wait();
+    assert!(!process.status().success());
+    assert!(!tmp_dir().join("killed/killed").exists());
+}
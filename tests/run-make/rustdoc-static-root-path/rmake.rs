@@ -0,0 +1,20 @@
+// Check that `static_root_path` makes the pages load the stylesheets from the given root, and
+// that `assert_static_root` rejects pages which load them from the output directory.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_has_xpath, assert_static_root, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("cdn");
+    rustdoc().input("foo.rs").out_dir(&out_dir).static_root_path("/cdn/").run();
+    let page = out_dir.join("foo/struct.Foo.html");
+    assert_static_root(&page, "/cdn/");
+    assert_has_xpath(&page, "//link[@rel='stylesheet' and starts-with(@href, '/cdn/rustdoc-')]");
+
+    let plain = tmp_dir().join("plain");
+    rustdoc().input("foo.rs").out_dir(&plain).run();
+    let page = plain.join("foo/struct.Foo.html");
+    assert!(panic::catch_unwind(|| assert_static_root(&page, "/cdn/")).is_err());
+}
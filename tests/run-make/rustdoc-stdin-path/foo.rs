@@ -0,0 +1,3 @@
+//! Documented from stdin.
+
+pub struct FromStdin;
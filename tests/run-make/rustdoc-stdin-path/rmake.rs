@@ -0,0 +1,35 @@
+// Check that `stdin_path` and `stdin` feed rustdoc's standard input, and that `stdin` neither
+// blocks nor fails when rustdoc doesn't read it all while filling its output.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("-").crate_name("foo").stdin_path("foo.rs").out_dir(&out_dir).run();
+    assert!(out_dir.join("foo/struct.FromStdin.html").exists());
+
+    rustdoc()
+        .input("-")
+        .crate_name("bar")
+        .stdin(fs::read("foo.rs").unwrap())
+        .out_dir(&out_dir)
+        .run();
+    assert!(out_dir.join("bar/struct.FromStdin.html").exists());
+
+    let mut undocumented = String::from("#![warn(missing_docs)]\n");
+    for i in 0..5000 {
+        undocumented.push_str(&format!("pub fn undocumented_{i}() {{}}\n"));
+    }
+    let undocumented_rs = tmp_dir().join("undocumented.rs");
+    fs::write(&undocumented_rs, undocumented).unwrap();
+    rustdoc()
+        .input(&undocumented_rs)
+        .stdin(vec![b'\n'; 1 << 20])
+        .out_dir(&out_dir)
+        .run_and_capture()
+        .assert_exit_code(0)
+        .assert_stderr_contains("missing documentation for a function");
+}
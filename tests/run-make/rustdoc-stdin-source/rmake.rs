@@ -0,0 +1,30 @@
+// Check that `stdin_source` has rustdoc document the crate read from the `-` input, with its name
+// and type set, and that the crate name is validated like with `crate_name`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, CrateType};
+use std::ffi::OsStr;
+use std::{fs, panic};
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.stdin_source("pub struct FromStdin;", "snippet", CrateType::Lib);
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    let expected = ["-", "--crate-name", "snippet", "--crate-type", "lib"];
+    assert!(args.ends_with(&expected.map(OsStr::new)));
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc()
+        .stdin_source("//! Read from stdin.\n\npub struct FromStdin;\n", "snippet", CrateType::Lib)
+        .out_dir(&out_dir)
+        .run();
+    assert!(out_dir.join("snippet/struct.FromStdin.html").exists());
+    let index = fs::read_to_string(out_dir.join("snippet/index.html")).unwrap();
+    assert!(index.contains("Read from stdin."));
+
+    let invalid = panic::catch_unwind(|| {
+        rustdoc().stdin_source("pub struct FromStdin;", "not-valid", CrateType::Lib);
+    });
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,24 @@
+// Check that `sysroot` passes `--sysroot {path}` and that `crate_version` passes the version as a
+// single argument, even when it contains spaces.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.sysroot("/opt/sysroot");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--sysroot", "/opt/sysroot"].map(OsStr::new)));
+
+    let mut doc = rustdoc();
+    doc.crate_version("1.2.3");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--crate-version", "1.2.3"].map(OsStr::new)));
+
+    let mut doc = rustdoc();
+    doc.crate_version("1.2.3 (nightly build)");
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--crate-version", "1.2.3 (nightly build)"].map(OsStr::new)));
+}
@@ -0,0 +1,4 @@
+/// ```
+/// assert!(cfg!(target_feature = "crt-static"));
+/// ```
+pub fn foo() {}
@@ -0,0 +1,38 @@
+// Check that `target_cpu` passes `-C target-cpu`, and that `target_feature` joins the features of
+// several calls, in order, in a single `-C target-feature` which the doctests are compiled with.
+// The arguments rustdoc is spawned with are read back from the arg file of `use_arg_file`, and
+// `crt-static` is off by default on this target, so the doctest only passes if they reach it.
+//@ only-x86_64-unknown-linux-gnu
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+use std::fs;
+
+fn codegen_opts<'a>(args: impl IntoIterator<Item = &'a OsStr>) -> Vec<String> {
+    let args: Vec<_> = args.into_iter().collect();
+    args.windows(2)
+        .filter(|pair| pair[0] == "-C")
+        .map(|pair| pair[1].to_string_lossy().into_owned())
+        .collect()
+}
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.input("foo.rs").arg("--test").target_cpu("generic");
+    doc.inspect(|cmd| assert_eq!(codegen_opts(cmd.get_args()), ["target-cpu=generic"]));
+
+    let mut enabled = doc.clone();
+    enabled.target_feature("-crt-static").codegen("debuginfo=0").target_feature("+crt-static");
+    let summary = enabled.clone().use_arg_file().run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+    let arg_file = fs::read_to_string(tmp_dir().join("rustdoc-args-0.txt")).unwrap();
+    assert_eq!(
+        codegen_opts(arg_file.lines().map(OsStr::new)),
+        ["target-cpu=generic", "debuginfo=0", "target-feature=-crt-static,+crt-static"]
+    );
+
+    let summary = doc.target_feature("-crt-static").run_doctests();
+    assert_eq!((summary.passed, summary.failed), (0, 1));
+}
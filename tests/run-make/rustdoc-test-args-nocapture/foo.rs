@@ -0,0 +1,4 @@
+/// ```
+/// println!("printed by the doctest");
+/// ```
+pub fn foo() {}
@@ -0,0 +1,25 @@
+// Check that `test_args` passes each argument through its own `--test-args`, in order, and that
+// `nocapture` lets the output of the doctests through, unlike `--test-args --nocapture`.
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.input("foo.rs").arg("--test").test_args("--test-threads=1").test_args("--nocapture");
+    doc.inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().skip_while(|&arg| arg != "--test-args").collect();
+        assert_eq!(args, ["--test-args", "--test-threads=1", "--test-args", "--nocapture"]);
+    });
+    let captured = doc.run_and_capture();
+    assert!(!captured.stdout_utf8().contains("printed by the doctest"));
+    captured.assert_stdout_contains("test result: ok. 1 passed");
+
+    rustdoc()
+        .input("foo.rs")
+        .arg("--test")
+        .nocapture()
+        .run_and_capture()
+        .assert_stdout_contains("printed by the doctest");
+}
@@ -0,0 +1,4 @@
+/// ```
+/// assert_eq!(2 + 2, 4);
+/// ```
+pub fn foo() {}
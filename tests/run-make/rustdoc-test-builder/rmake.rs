@@ -0,0 +1,43 @@
+// Check that `test_builder` and `test_builder_wrapper` pass their flags, and that the doctests
+// are compiled by the given builder through the given wrapper, here a script logging its
+// arguments.
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+fn main() {
+    rustdoc().test_builder("my-rustc").test_builder_wrapper("my-wrapper").inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().skip(1).collect();
+        let expected = [
+            "-Z",
+            "unstable-options",
+            "--test-builder",
+            "my-rustc",
+            "--test-builder-wrapper",
+            "my-wrapper",
+        ];
+        assert_eq!(args, expected.map(OsStr::new));
+    });
+
+    let wrapper = tmp_dir().join("wrapper.sh");
+    let log = tmp_dir().join("wrapper.log");
+    fs::write(&wrapper, format!("#!/bin/sh\necho \"$@\" >> '{}'\nexec \"$@\"\n", log.display()))
+        .unwrap();
+    fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let rustc = env::var("RUSTC").unwrap();
+    let summary = rustdoc()
+        .input("doctest.rs")
+        .test_builder(&rustc)
+        .test_builder_wrapper(&wrapper)
+        .run_doctests();
+    assert_eq!((summary.passed, summary.failed), (1, 0));
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(logged.starts_with(&rustc), "the wrapper didn't run the builder: {logged}");
+}
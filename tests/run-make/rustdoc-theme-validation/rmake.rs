@@ -0,0 +1,34 @@
+// Check that `theme` adds each custom theme and `default_theme` picks one of them as the default,
+// and that `theme` rejects files which are missing or don't have the `.css` extension.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_xpath_attr, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let single = tmp_dir().join("single");
+    rustdoc().input("foo.rs").out_dir(&single).theme("ocean.css").run();
+    assert!(single.join("ocean.css").exists());
+    let page = single.join("foo/fn.foo.html");
+    assert_xpath_attr(&page, "//meta[@name='rustdoc-vars']", "data-themes", "ocean");
+
+    let two = tmp_dir().join("two");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&two)
+        .theme("ocean.css")
+        .theme("forest.css")
+        .default_theme("forest")
+        .run();
+    let page = two.join("foo/fn.foo.html");
+    assert_xpath_attr(&page, "//meta[@name='rustdoc-vars']", "data-themes", "forest,ocean");
+    assert_xpath_attr(&page, "//script[@id='default-settings']", "data-theme", "forest");
+
+    for theme in ["not-css.txt", "missing.css"] {
+        let rejected = panic::catch_unwind(|| {
+            rustdoc().theme(theme);
+        });
+        assert!(rejected.is_err(), "`{theme}` was accepted");
+    }
+}
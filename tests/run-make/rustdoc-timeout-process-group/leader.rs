@@ -0,0 +1,7 @@
+/// ```
+/// let stat = std::fs::read_to_string("/proc/self/stat").unwrap();
+/// // The parent and the process group follow the state, after the parenthesized command name.
+/// let fields: Vec<_> = stat.rsplit_once(')').unwrap().1.split_whitespace().collect();
+/// assert_ne!(fields[1], fields[2], "rustdoc leads a process group");
+/// ```
+pub fn foo() {}
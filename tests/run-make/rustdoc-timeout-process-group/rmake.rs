@@ -0,0 +1,18 @@
+// Check that `timeout` runs rustdoc in a process group of its own only for that invocation: the
+// doctest fails when its parent rustdoc leads its process group, as it does within `timeout`.
+//@ only-linux
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::time::Duration;
+
+fn main() {
+    let mut doc = rustdoc();
+    doc.input("leader.rs").arg("--test");
+    let grouped = doc.timeout(Duration::from_secs(60));
+    assert!(!grouped.timed_out());
+    grouped.assert_exit_code(101).assert_stdout_contains("rustdoc leads a process group");
+
+    doc.run();
+}
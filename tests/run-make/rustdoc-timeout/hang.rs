@@ -0,0 +1,4 @@
+/// ```
+/// std::thread::sleep(std::time::Duration::from_secs(600));
+/// ```
+pub fn foo() {}
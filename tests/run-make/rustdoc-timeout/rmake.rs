@@ -0,0 +1,20 @@
+// Check that `timeout` kills a rustdoc invocation hanging on a doctest, and lets a quick one
+// finish.
+//@ ignore-windows `timeout` is only available on unix
+
+extern crate run_make_support;
+
+use run_make_support::rustdoc;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let start = Instant::now();
+    let hanging = rustdoc().input("hang.rs").arg("--test").timeout(Duration::from_secs(3));
+    assert!(hanging.timed_out());
+    assert!(!hanging.status().success());
+    assert!(start.elapsed() < Duration::from_secs(60));
+
+    let quick = rustdoc().arg("--version").timeout(Duration::from_secs(60));
+    assert!(!quick.timed_out());
+    quick.assert_exit_code(0).assert_stdout_contains("rustdoc");
+}
@@ -0,0 +1,22 @@
+// Check that `try_extern_` passes `--extern {crate_name}={path}` for valid crate names, and
+// returns an `ExternError` naming the first whitespace or path separator it finds otherwise.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, ExternError};
+use std::ffi::OsStr;
+
+fn main() {
+    let mut doc = rustdoc();
+    assert!(doc.try_extern_("dep", "libdep.rlib").is_ok());
+    let args: Vec<_> = doc.command_mut().get_args().collect();
+    assert!(args.ends_with(&["--extern", "dep=libdep.rlib"].map(OsStr::new)));
+
+    for (crate_name, character) in [("not valid", ' '), ("dep\\sub", '\\'), ("dep/sub", '/')] {
+        let mut doc = rustdoc();
+        let err = doc.try_extern_(crate_name, "libdep.rlib").unwrap_err();
+        assert_eq!(err, ExternError { crate_name: crate_name.to_string(), character });
+        let args: Vec<_> = doc.command_mut().get_args().collect();
+        assert!(!args.contains(&OsStr::new("--extern")), "`{crate_name}` was passed");
+    }
+}
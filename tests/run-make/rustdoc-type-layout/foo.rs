@@ -0,0 +1,9 @@
+#[repr(C)]
+pub struct Pair {
+    pub a: u64,
+    pub b: u32,
+}
+
+pub struct Byte(pub u8);
+
+pub struct Zst;
@@ -0,0 +1,26 @@
+// Check that `show_type_layout` adds a layout section to the type pages, and that
+// `assert_type_layout` checks the size it displays, zero-sized types included.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_type_layout, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("layout");
+    rustdoc().input("foo.rs").out_dir(&out_dir).show_type_layout().run();
+    let pair = out_dir.join("foo/struct.Pair.html");
+    assert_type_layout(&pair, "16 bytes");
+    assert_type_layout(&pair, "16");
+    assert_type_layout(out_dir.join("foo/struct.Byte.html"), "1");
+    assert_type_layout(out_dir.join("foo/struct.Zst.html"), "0 bytes");
+    assert_type_layout(out_dir.join("foo/struct.Zst.html"), "0");
+    let wrong = panic::catch_unwind(|| assert_type_layout(&pair, "8"));
+    assert!(wrong.is_err());
+
+    let plain = tmp_dir().join("plain");
+    rustdoc().input("foo.rs").out_dir(&plain).run();
+    let zst = plain.join("foo/struct.Zst.html");
+    let missing = panic::catch_unwind(|| assert_type_layout(&zst, "0"));
+    assert!(missing.is_err());
+}
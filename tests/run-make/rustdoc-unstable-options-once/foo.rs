@@ -0,0 +1,2 @@
+/// Docs.
+pub fn foo() {}
@@ -0,0 +1,28 @@
+// Check that the builder methods requiring `-Z unstable-options` only pass it once, even when
+// several of them are combined, and that `unstable_flag` deduplicates other `-Z` flags too.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir, OutputFormat};
+
+fn main() {
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(tmp_dir())
+        .arg("-Zunstable-options")
+        .output_format_typed(OutputFormat::Json)
+        .document_hidden_items()
+        .check()
+        .unstable_flag("force-unstable-if-unmarked")
+        .unstable_flag("force-unstable-if-unmarked")
+        .inspect(|cmd| {
+            let args: Vec<_> = cmd.get_args().map(|arg| arg.to_str().unwrap()).collect();
+            let count = |flag| {
+                args.iter().filter(|arg| **arg == format!("-Z{flag}")).count()
+                    + args.windows(2).filter(|pair| pair[0] == "-Z" && pair[1] == flag).count()
+            };
+            assert_eq!(count("unstable-options"), 1, "{args:?}");
+            assert_eq!(count("force-unstable-if-unmarked"), 1, "{args:?}");
+        })
+        .run();
+}
@@ -0,0 +1 @@
+pub const INHERITED: &str = env!("RUSTDOC_ARG_FILE_INHERITED");
@@ -0,0 +1,5 @@
+#[cfg(generated_cfg_0)]
+pub struct First;
+
+#[cfg(generated_cfg_4999)]
+pub struct Last;
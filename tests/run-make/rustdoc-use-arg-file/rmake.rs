@@ -0,0 +1,38 @@
+// Check that `use_arg_file` passes the arguments of an invocation too long for some command
+// lines through an arg file, leaving only `@{file}` on the command line. The command passing it
+// keeps the environment of the invocation, including when it was cleared.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::{env, fs};
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    let mut doc = rustdoc();
+    doc.input("foo.rs").out_dir(&out_dir);
+    for i in 0..5000 {
+        doc.cfg(&format!("generated_cfg_{i}"));
+    }
+    doc.use_arg_file().run();
+    assert!(out_dir.join("foo/struct.First.html").exists());
+    assert!(out_dir.join("foo/struct.Last.html").exists());
+
+    let arg_files: Vec<_> = fs::read_dir(tmp_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().unwrap().to_string_lossy().starts_with("rustdoc-args-"))
+        .collect();
+    let [arg_file] = &arg_files[..] else { panic!("expected one arg file, found {arg_files:?}") };
+    let args = fs::read_to_string(arg_file).unwrap();
+    assert_eq!(args.lines().filter(|&arg| arg == "--cfg").count(), 5000);
+    assert!(args.lines().any(|arg| arg == "generated_cfg_4999"));
+
+    env::set_var("RUSTDOC_ARG_FILE_INHERITED", "1");
+    let mut cleared = rustdoc();
+    cleared.input("env.rs").out_dir(&out_dir).env_clear().use_arg_file();
+    cleared
+        .run_fail_with_code(1)
+        .assert_stderr_contains("environment variable `RUSTDOC_ARG_FILE_INHERITED` not defined");
+    cleared.env("RUSTDOC_ARG_FILE_INHERITED", "1").run();
+}
@@ -0,0 +1,27 @@
+// Check that `rustdoc_version` parses the version of the rustdoc under test, and that the short
+// `--version` agrees with it.
+
+extern crate run_make_support;
+
+use run_make_support::{bare_rustdoc, rustdoc_version};
+
+fn main() {
+    let version = rustdoc_version();
+    assert_eq!(version.major, 1);
+    assert!(
+        ["stable", "beta", "nightly", "dev"].contains(&version.channel.as_str()),
+        "{version:?}"
+    );
+    if let Some(hash) = &version.commit_hash {
+        assert_eq!(hash.len(), 40);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()), "{hash}");
+    }
+    assert_eq!(rustdoc_version(), version);
+
+    let short = bare_rustdoc().arg("--version").run_and_capture().stdout_utf8();
+    let number = format!("rustdoc {}.{}.{}", version.major, version.minor, version.patch);
+    assert!(short.starts_with(&number), "{short}");
+    if version.channel != "stable" {
+        assert!(short.contains(&format!("-{}", version.channel)), "{short}");
+    }
+}
@@ -0,0 +1,6 @@
+pub fn public() {}
+
+fn private() {}
+
+#[doc(hidden)]
+pub fn hidden() {}
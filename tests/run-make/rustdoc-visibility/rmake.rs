@@ -0,0 +1,45 @@
+// Check that `document_private_items`, `document_hidden_items` and `document_visibility` pass
+// their flags, with a single `-Z unstable-options` for the hidden items, and that the private and
+// hidden items get documented.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::ffi::OsStr;
+
+fn args_of(private: bool, hidden: bool) -> Vec<String> {
+    let mut doc = rustdoc();
+    doc.document_visibility(private, hidden);
+    doc.command_mut().get_args().map(|arg| arg.to_str().unwrap().to_owned()).collect()
+}
+
+fn main() {
+    let mut private = rustdoc();
+    private.document_private_items();
+    let args: Vec<_> = private.command_mut().get_args().collect();
+    assert!(args.ends_with(&[OsStr::new("--document-private-items")]));
+    assert!(!args.contains(&OsStr::new("unstable-options")));
+
+    let mut hidden = rustdoc();
+    hidden.document_hidden_items();
+    let args: Vec<_> = hidden.command_mut().get_args().collect();
+    assert!(args.ends_with(&["-Z", "unstable-options", "--document-hidden-items"].map(OsStr::new)));
+
+    let count = |args: &[String], flag: &str| args.iter().filter(|arg| *arg == flag).count();
+    let both = args_of(true, true);
+    assert_eq!(count(&both, "--document-private-items"), 1);
+    assert_eq!(count(&both, "--document-hidden-items"), 1);
+    assert_eq!(count(&both, "unstable-options"), 1);
+    hidden.document_hidden_items();
+    let args: Vec<_> = hidden.command_mut().get_args().collect();
+    assert_eq!(args.iter().filter(|arg| **arg == "unstable-options").count(), 1);
+    assert_eq!(count(&args_of(true, false), "--document-hidden-items"), 0);
+    assert_eq!(count(&args_of(false, true), "--document-private-items"), 0);
+    assert!(args_of(false, false).iter().all(|arg| !arg.starts_with("--document-")));
+
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).document_visibility(true, true).run();
+    for item in ["public", "private", "hidden"] {
+        assert!(out_dir.join(format!("foo/fn.{item}.html")).exists(), "`{item}` is missing");
+    }
+}
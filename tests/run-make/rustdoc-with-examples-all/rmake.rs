@@ -0,0 +1,28 @@
+// Check that `with_examples_all` passes a `--with-examples` per calls file in sorted order, and
+// that a missing calls file panics.
+
+extern crate run_make_support;
+
+use run_make_support::{rustdoc, tmp_dir};
+use std::{fs, panic};
+
+fn main() {
+    let (first, second) = (tmp_dir().join("a.calls"), tmp_dir().join("b.calls"));
+    fs::write(&first, "").unwrap();
+    fs::write(&second, "").unwrap();
+
+    rustdoc().with_examples_all([&second, &first]).inspect(|cmd| {
+        let args: Vec<_> = cmd.get_args().collect();
+        let calls: Vec<_> = args
+            .windows(2)
+            .filter(|pair| pair[0] == "--with-examples")
+            .map(|pair| pair[1])
+            .collect();
+        assert_eq!(calls, [&first, &second]);
+    });
+
+    let missing = panic::catch_unwind(|| {
+        rustdoc().with_examples_all([first.clone(), tmp_dir().join("missing.calls")]);
+    });
+    assert!(missing.is_err());
+}
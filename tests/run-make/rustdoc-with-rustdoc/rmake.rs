@@ -0,0 +1,33 @@
+// Check that `Rustdoc::with_rustdoc` and `Rustdoc::bare_with_rustdoc` run the given binary
+// rather than `$RUSTDOC`, here a wrapper script recording its arguments before running rustdoc.
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::{tmp_dir, Rustdoc};
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+fn main() {
+    let wrapper = tmp_dir().join("rustdoc-wrapper.sh");
+    let log = tmp_dir().join("wrapper.log");
+    let script = format!(
+        "#!/bin/sh\necho \"$@\" >> '{}'\nexec '{}' \"$@\"\n",
+        log.display(),
+        env::var("RUSTDOC").unwrap()
+    );
+    fs::write(&wrapper, script).unwrap();
+    fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let out_dir = tmp_dir().join("doc");
+    Rustdoc::with_rustdoc(&wrapper).input("foo.rs").out_dir(&out_dir).run();
+    assert!(out_dir.join("foo/struct.Foo.html").exists());
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(logged.contains("foo.rs"));
+    assert!(logged.starts_with("-L"), "the target library path is missing: {logged}");
+
+    Rustdoc::bare_with_rustdoc(&wrapper).arg("--version").run();
+    let logged = fs::read_to_string(&log).unwrap();
+    assert_eq!(logged.lines().last(), Some("--version"));
+}
@@ -0,0 +1,4 @@
+/// See [`Foo`].
+pub fn bar() {}
+
+pub struct Foo;
@@ -0,0 +1,30 @@
+// Check that `assert_xpath_attr` checks the value of an attribute of the selected elements, and
+// tells a wrong value from a missing attribute.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_xpath_attr, rustdoc, tmp_dir};
+use std::panic;
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    *panic::catch_unwind(f).unwrap_err().downcast::<String>().unwrap()
+}
+
+fn main() {
+    rustdoc().input("foo.rs").out_dir(tmp_dir()).run();
+    let page = tmp_dir().join("foo/fn.bar.html");
+    assert_xpath_attr(&page, "//a[code='Foo']", "href", "struct.Foo.html");
+    assert_xpath_attr(&page, "//section[@class='content']", "id", "main-content");
+    assert_xpath_attr(&page, "//section", "id", "main-content");
+
+    let wrong =
+        panic_message(|| assert_xpath_attr(&page, "//a[code='Foo']", "href", "enum.Foo.html"));
+    assert!(
+        wrong.contains("is not `enum.Foo.html`") && wrong.contains("struct.Foo.html"),
+        "{wrong}"
+    );
+    let missing = panic_message(|| assert_xpath_attr(&page, "//a[code='Foo']", "id", "foo"));
+    assert!(missing.contains("has an `id` attribute"), "{missing}");
+    let unmatched = panic_message(|| assert_xpath_attr(&page, "//blink", "id", "foo"));
+    assert!(unmatched.contains("did not match any element"), "{unmatched}");
+}
@@ -0,0 +1,10 @@
+#![crate_name = "foo"]
+#![feature(effects, const_trait_impl)]
+
+#[const_trait]
+pub trait Tr {
+    fn f();
+}
+
+/// foo
+pub const fn g<T: ~const Tr>() {}
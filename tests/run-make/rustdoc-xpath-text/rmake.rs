@@ -0,0 +1,36 @@
+// Check that `assert_xpath_text`, `assert_no_xpath` and `assert_no_xpath_text` look at the
+// whitespace-normalized text of the selected nodes, reproducing the
+// `tests/rustdoc/const-effect-param.rs` assertions: the host effect parameter of `g` must not be
+// rendered.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_no_xpath, assert_no_xpath_text, assert_xpath_text, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("const-effect-param.rs").out_dir(&out_dir).run();
+    let page = out_dir.join("foo/fn.g.html");
+    let decl = r#"//pre[@class="rust item-decl"]"#;
+
+    assert_xpath_text(&page, decl, "pub const fn g<T: Tr>()");
+    assert_xpath_text(&page, decl, "pub  const\n fn g");
+    assert_no_xpath_text(&page, decl, "~const");
+    assert_no_xpath_text(&page, decl, "host");
+    assert_no_xpath(&page, r#"//pre[@class="rust item-decl"]//span[@class="where"]"#);
+    assert_no_xpath(&page, r#"//a[text()="host"]"#);
+
+    let found = panic::catch_unwind(|| {
+        assert_xpath_text(&page, decl, "pub const fn g<T: ~const Tr>()");
+    });
+    assert!(found.is_err());
+    let found = panic::catch_unwind(|| {
+        assert_no_xpath(&page, decl);
+    });
+    assert!(found.is_err());
+    let found = panic::catch_unwind(|| {
+        assert_no_xpath_text(&page, decl, "const  fn");
+    });
+    assert!(found.is_err());
+}
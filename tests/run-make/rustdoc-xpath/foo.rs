@@ -0,0 +1,9 @@
+pub struct Bar;
+
+impl Bar {
+    pub fn first(&self) {}
+    pub fn second(&self) {}
+    pub fn third(&self) {}
+}
+
+pub const fn g<T>() {}
@@ -0,0 +1,32 @@
+// Check that `assert_has_xpath` and `assert_xpath_count` find the nodes of a generated page, and
+// panic when an XPath doesn't match anything or matches the wrong number of nodes.
+
+extern crate run_make_support;
+
+use run_make_support::{assert_has_xpath, assert_xpath_count, rustdoc, tmp_dir};
+use std::panic;
+
+fn main() {
+    let out_dir = tmp_dir().join("doc");
+    rustdoc().input("foo.rs").out_dir(&out_dir).run();
+    let page = out_dir.join("foo/struct.Bar.html");
+
+    assert_has_xpath(&page, r#"//pre[@class="rust item-decl"]"#);
+    assert_has_xpath(&page, r#"//*[@id="method.first"]//h4[@class="code-header"]"#);
+    assert_has_xpath(&page, r##"//section[@id="method.second"]/h4/a[@href="#method.second"]"##);
+    assert_has_xpath(&page, "//head/title");
+    assert_has_xpath(&page, r#"//a[contains(@class, "fn") and text()="third"]"#);
+
+    let missing = panic::catch_unwind(|| {
+        assert_has_xpath(&page, r#"//pre[@class="not-a-class"]"#);
+    });
+    assert!(missing.is_err());
+
+    assert_xpath_count(&page, r#"//section[@class="method"]"#, 3);
+    assert_xpath_count(&page, r#"//*[@class="impl-items"]/section[2]"#, 1);
+    assert_xpath_count(&page, "//blink", 0);
+    let wrong_count = panic::catch_unwind(|| {
+        assert_xpath_count(&page, r#"//section[@class="method"]"#, 2);
+    });
+    assert!(wrong_count.is_err());
+}